@@ -2,12 +2,11 @@
 
 import { useState } from "react";
 import Graph from "graphology";
-import { LinkGraph } from "../model/savefile-model";
-import { deserializeCoordinates, transposeCoordinates } from "../model/coordinates";
+import { transposeCoordinates } from "../model/coordinates";
 import { Map } from "./map";
 import dynamic from "next/dynamic";
 import { useDropzone } from "react-dropzone";
-import init, { load_file } from "../../savegame-reader/pkg";
+import init, { SaveGame } from "../../savegame-reader/pkg";
 
 export const HomePage = dynamic(
   async () => {
@@ -25,22 +24,23 @@ export const HomePage = dynamic(
         const graph = new Graph({ type: "directed", multi: false, allowSelfLoops: false });
 
         const buf = new Uint8Array(await file.arrayBuffer());
-        const allGraphs: LinkGraph[] = JSON.parse(load_file(buf));
-        const passengerGraphs = allGraphs.filter((graph) => graph.cargo === 0);
+        const saveGame = new SaveGame(buf);
+        const allGraphs = saveGame.linkGraphs();
+        const passengers = saveGame.cargoTable().passengers;
+        const mapSizeX = saveGame.mapWidth();
+        const mapSizeY = saveGame.mapHeight();
+        saveGame.free();
+        const passengerGraphs = allGraphs.filter((graph) => graph.cargo === passengers);
 
         for (const nodes of passengerGraphs.map((graph) => graph.nodes)) {
           for (const node of nodes) {
-            // TODO read from savefile
-            const mapSizeX = 1024;
-            const mapSizeY = 1024;
-
-            const coords = deserializeCoordinates(node, mapSizeX);
-            graph.mergeNode(node.station, transposeCoordinates(mapSizeX, coords, mapSizeY));
+            graph.mergeNode(node.station, {
+              ...transposeCoordinates(mapSizeX, node, mapSizeY),
+              label: node.name,
+            });
 
             for (const edge of node.edges) {
-              if ((edge.next_edge ?? edge.dest_node) === 65535) break;
-
-              const destination = nodes[edge.next_edge ?? edge.dest_node];
+              const destination = nodes[edge.dest_node];
               graph.mergeNode(destination.station);
               graph.addEdge(node.station, destination.station);
             }
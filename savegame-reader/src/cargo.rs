@@ -0,0 +1,347 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::loader::Chunk;
+use crate::save_game::SaveGame;
+use crate::tile::Tile;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CARGO_TS: &'static str = r#"
+export type Climate = "Temperate" | "SubArctic" | "SubTropical" | "Toyland";
+
+export interface CargoSpec {
+  slot: number;
+  label?: string;
+  name?: string;
+}
+
+export interface CargoTable {
+  climate: Climate;
+  newgrf_cargos: boolean;
+  passengers?: number;
+  cargos: CargoSpec[];
+}
+"#;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum Climate {
+    Temperate,
+    SubArctic,
+    SubTropical,
+    Toyland,
+}
+
+const CLIMATES: [Climate; 4] = [
+    Climate::Temperate,
+    Climate::SubArctic,
+    Climate::SubTropical,
+    Climate::Toyland,
+];
+
+impl Climate {
+    pub fn from_landscape(landscape: u8) -> Option<Climate> {
+        CLIMATES.get(usize::from(landscape)).copied()
+    }
+
+    // Each climate has its own set of tree types, see OpenTTD's TreeType
+    fn from_tree_type(tree_type: u8) -> Option<Climate> {
+        Some(match tree_type {
+            0..=11 => Climate::Temperate,
+            12..=19 => Climate::SubArctic,
+            20..=31 => Climate::SubTropical,
+            32..=40 => Climate::Toyland,
+            _ => return None,
+        })
+    }
+}
+
+// The slot of passengers in every climate
+const CT_PASSENGERS: u8 = 0;
+
+// The cargo slots of each climate without NewGRFs, mirroring OpenTTD's _default_climate_cargo.
+// Empty slots have no label.
+const CLIMATE_CARGOS: [[Option<(&str, &str)>; 12]; 4] = [
+    [
+        Some(("PASS", "Passengers")),
+        Some(("COAL", "Coal")),
+        Some(("MAIL", "Mail")),
+        Some(("OIL_", "Oil")),
+        Some(("LVST", "Livestock")),
+        Some(("GOOD", "Goods")),
+        Some(("GRAI", "Grain")),
+        Some(("WOOD", "Wood")),
+        Some(("IORE", "Iron Ore")),
+        Some(("STEL", "Steel")),
+        Some(("VALU", "Valuables")),
+        None,
+    ],
+    [
+        Some(("PASS", "Passengers")),
+        Some(("COAL", "Coal")),
+        Some(("MAIL", "Mail")),
+        Some(("OIL_", "Oil")),
+        Some(("LVST", "Livestock")),
+        Some(("GOOD", "Goods")),
+        Some(("WHEA", "Wheat")),
+        Some(("WOOD", "Wood")),
+        None,
+        Some(("PAPR", "Paper")),
+        Some(("GOLD", "Gold")),
+        Some(("FOOD", "Food")),
+    ],
+    [
+        Some(("PASS", "Passengers")),
+        Some(("RUBR", "Rubber")),
+        Some(("MAIL", "Mail")),
+        Some(("OIL_", "Oil")),
+        Some(("FRUT", "Fruit")),
+        Some(("GOOD", "Goods")),
+        Some(("MAIZ", "Maize")),
+        Some(("WOOD", "Wood")),
+        Some(("CORE", "Copper Ore")),
+        Some(("WATR", "Water")),
+        Some(("DIAM", "Diamonds")),
+        Some(("FOOD", "Food")),
+    ],
+    [
+        Some(("PASS", "Passengers")),
+        Some(("SUGR", "Sugar")),
+        Some(("MAIL", "Mail")),
+        Some(("TOYS", "Toys")),
+        Some(("BATT", "Batteries")),
+        Some(("SWET", "Sweets")),
+        Some(("TOFF", "Toffee")),
+        Some(("COLA", "Cola")),
+        Some(("CTCD", "Candyfloss")),
+        Some(("BUBL", "Bubbles")),
+        Some(("PLST", "Plastic")),
+        Some(("FZDR", "Fizzy Drinks")),
+    ],
+];
+
+// The label and name are unknown for cargos that may have been defined by a NewGRF
+#[derive(Debug, Clone, Serialize)]
+pub struct CargoSpec {
+    pub slot: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+// Savegames don't store which cargo a slot holds, OpenTTD sets the cargos up again from the
+// climate and the NewGRFs when loading. Without NewGRFs the slots follow from the climate. With
+// NewGRFs `newgrf_cargos` is set and only the slots the savegame uses are known, without labels.
+// `passengers` is the slot passengers are carried in, which NewGRF cargo sets keep in slot 0 like
+// every climate does.
+#[derive(Debug, Clone, Serialize)]
+pub struct CargoTable {
+    pub climate: Climate,
+    pub newgrf_cargos: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passengers: Option<u8>,
+    pub cargos: Vec<CargoSpec>,
+}
+
+impl CargoTable {
+    pub fn for_climate(climate: Climate) -> CargoTable {
+        CargoTable {
+            climate,
+            newgrf_cargos: false,
+            passengers: Some(CT_PASSENGERS),
+            cargos: CLIMATE_CARGOS[climate as usize]
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, cargo)| {
+                    cargo.map(|(label, name)| CargoSpec {
+                        slot: slot as u8,
+                        label: Some(String::from(label)),
+                        name: Some(String::from(name)),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn from_save_game(save_game: &SaveGame) -> CargoTable {
+        let newgrf_cargos = match save_game.chunks.get("NGRF") {
+            Some(Chunk::Table(items)) => !items.is_empty(),
+            Some(Chunk::Raw { items, .. }) => *items > 0,
+            _ => false,
+        };
+        if !newgrf_cargos {
+            return CargoTable::for_climate(save_game.climate());
+        }
+        let slots = used_cargo_slots(save_game);
+        CargoTable {
+            climate: save_game.climate(),
+            newgrf_cargos,
+            passengers: slots.contains(&CT_PASSENGERS).then_some(CT_PASSENGERS),
+            cargos: slots
+                .into_iter()
+                .map(|slot| CargoSpec {
+                    slot,
+                    label: None,
+                    name: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, slot: u8) -> Option<&CargoSpec> {
+        self.cargos.iter().find(|cargo| cargo.slot == slot)
+    }
+
+    pub fn by_label(&self, label: &str) -> Option<&CargoSpec> {
+        self.cargos
+            .iter()
+            .find(|cargo| cargo.label.as_deref() == Some(label))
+    }
+}
+
+// The cargo slots referred to by link graphs, industries and vehicles
+fn used_cargo_slots(save_game: &SaveGame) -> BTreeSet<u8> {
+    let mut slots = BTreeSet::new();
+    slots.extend(
+        save_game
+            .items("LGRP")
+            .iter()
+            .filter_map(|graph| graph.integer("cargo"))
+            .map(|cargo| cargo as u8),
+    );
+    for industry in save_game.industries().unwrap_or_default() {
+        slots.extend(industry.produced.iter().map(|produced| produced.cargo));
+        slots.extend(industry.accepted.iter().map(|accepted| accepted.cargo));
+    }
+    slots.extend(
+        save_game
+            .vehicles()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|vehicle| vehicle.details.as_ref())
+            .filter(|details| details.cargo_capacity > 0)
+            .map(|details| details.cargo_type),
+    );
+    slots
+}
+
+// Older savegames store their settings in a layout we don't decode, the climate is then told by
+// the trees growing on the map
+pub(crate) fn climate(save_game: &SaveGame) -> Climate {
+    if let Some(climate) = save_game
+        .items("PATS")
+        .first()
+        .and_then(|settings| settings.integer("game_creation.landscape"))
+        .and_then(|landscape| Climate::from_landscape(landscape as u8))
+    {
+        return climate;
+    }
+
+    let Ok(map) = save_game.tile_map() else {
+        return Climate::Temperate;
+    };
+    let mut counts = [0usize; 4];
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            if let Some(Tile::Trees { tree_type, .. }) = map.decode_tile(x, y) {
+                if let Some(climate) = Climate::from_tree_type(tree_type) {
+                    counts[climate as usize] += 1;
+                }
+            }
+        }
+    }
+    // Maps without trees are most likely temperate
+    CLIMATES
+        .into_iter()
+        .fold(Climate::Temperate, |best, climate| {
+            if counts[climate as usize] > counts[best as usize] {
+                climate
+            } else {
+                best
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::cargo::{CargoTable, Climate};
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+
+    #[test]
+    fn test_climate_cargos() {
+        let table = CargoTable::for_climate(Climate::SubArctic);
+        assert_eq!(table.get(6).unwrap().label.as_deref(), Some("WHEA"));
+        assert!(table.get(8).is_none());
+        assert_eq!(table.by_label("FOOD").unwrap().slot, 11);
+    }
+
+    #[test]
+    fn test_cargo_table_from_settings() {
+        let file = File::open("./test-big.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let table = CargoTable::from_save_game(&save_game);
+        // The savegame uses NewGRFs, which may have replaced the climate's cargos
+        assert!(table.newgrf_cargos);
+        assert!(table
+            .cargos
+            .iter()
+            .all(|cargo| cargo.label.is_none() && cargo.name.is_none()));
+        assert!(table.by_label("PASS").is_none());
+        assert_eq!(table.passengers, Some(0));
+        let cargos = save_game
+            .link_graphs()
+            .unwrap()
+            .iter()
+            .map(|graph| graph.cargo)
+            .collect::<Vec<_>>();
+        assert!(cargos.iter().all(|cargo| table.get(*cargo).is_some()));
+    }
+
+    #[test]
+    fn test_cargo_table_from_trees() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let table = CargoTable::from_save_game(&save_game);
+        assert_eq!(table.climate, Climate::SubArctic);
+        assert!(!table.newgrf_cargos);
+        assert_eq!(table.get(0).unwrap().name.as_deref(), Some("Passengers"));
+        assert_eq!(table.passengers, Some(0));
+        assert!(save_game
+            .link_graphs()
+            .unwrap()
+            .iter()
+            .all(|graph| table.get(graph.cargo).is_some()));
+    }
+
+    #[test]
+    fn test_passenger_link_graphs_with_newgrfs() {
+        let file = File::open("./test-big.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let passengers = save_game.cargo_table().passengers.unwrap();
+        let graphs = save_game
+            .link_graphs()
+            .unwrap()
+            .into_iter()
+            .filter(|graph| graph.cargo == passengers)
+            .collect::<Vec<_>>();
+        let edges = graphs
+            .iter()
+            .flat_map(|graph| {
+                graph
+                    .nodes
+                    .iter()
+                    .flat_map(|node| &node.edges)
+                    .map(move |edge| (graph, edge))
+            })
+            .collect::<Vec<_>>();
+        assert!(!edges.is_empty());
+        assert!(edges
+            .iter()
+            .all(|(graph, edge)| usize::from(edge.dest_node) < graph.nodes.len()));
+    }
+}
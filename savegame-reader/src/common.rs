@@ -1,8 +1,16 @@
-use std::mem::size_of;
+pub const OWNER_TOWN: u8 = 0x0F;
+pub const OWNER_NONE: u8 = 0x10;
+pub const OWNER_WATER: u8 = 0x11;
+
+// Years were stored as an offset from ORIGINAL_BASE_YEAR and dates as days since its start before
+// this version
+pub const SLV_31: u16 = 31;
+pub const ORIGINAL_BASE_YEAR: i32 = 1920;
+pub const DAYS_TILL_ORIGINAL_BASE_YEAR: i32 = 701_265;
 
 pub fn has_bit(input: usize, n: u8) -> bool {
-    let bits_in_type = size_of::<usize>() * 8;
-    if n > bits_in_type as u8 {
+    let bits_in_type = usize::BITS;
+    if u32::from(n) >= bits_in_type {
         panic!(
             "Tried to check for bit at {} but usize is only {} bits long",
             n, bits_in_type
@@ -12,17 +20,74 @@ pub fn has_bit(input: usize, n: u8) -> bool {
     input & (1 << n) != 0
 }
 
+// Scales a fraction of 255 to a percentage the way OpenTTD's ToPercent8 does
+pub fn to_percent8(value: u8) -> u8 {
+    ((u32::from(value) * 101) >> 8) as u8
+}
+
+// Scales a fraction of 65535 to a percentage the way OpenTTD's ToPercent16 does
+pub fn to_percent16(value: u16) -> u8 {
+    ((u32::from(value) * 101) >> 16) as u8
+}
+
+// Dates are stored as days since the 1st of January of year 0, which is a leap year
+pub fn year_of_date(date: i32) -> i32 {
+    const DAYS_IN_400_YEARS: i32 = 146_097;
+    let mut year = date.div_euclid(DAYS_IN_400_YEARS) * 400;
+    let mut days = date.rem_euclid(DAYS_IN_400_YEARS);
+    loop {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_year = if leap { 366 } else { 365 };
+        if days < days_in_year {
+            return year;
+        }
+        days -= days_in_year;
+        year += 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::common::has_bit;
+    use crate::common::{
+        has_bit, to_percent16, to_percent8, year_of_date, DAYS_TILL_ORIGINAL_BASE_YEAR,
+        ORIGINAL_BASE_YEAR,
+    };
 
     #[test]
     fn test_has_bit() {
-        assert_eq!(has_bit(0b1000_0000, 7), true);
-        assert_eq!(has_bit(0b0100_0000, 6), true);
-        assert_eq!(has_bit(0b0010_0000, 5), true);
-        assert_eq!(has_bit(0b0001_0000, 4), true);
-        assert_eq!(has_bit(0b0000_1000, 3), true);
-        assert_eq!(has_bit(0b1111_0000, 3), false);
+        assert!(has_bit(0b1000_0000, 7));
+        assert!(has_bit(0b0100_0000, 6));
+        assert!(has_bit(0b0010_0000, 5));
+        assert!(has_bit(0b0001_0000, 4));
+        assert!(has_bit(0b0000_1000, 3));
+        assert!(!has_bit(0b1111_0000, 3));
+    }
+
+    #[test]
+    fn test_to_percent8() {
+        assert_eq!(to_percent8(0), 0);
+        assert_eq!(to_percent8(128), 50);
+        assert_eq!(to_percent8(175), 69);
+        assert_eq!(to_percent8(255), 100);
+    }
+
+    #[test]
+    fn test_to_percent16() {
+        assert_eq!(to_percent16(0), 0);
+        assert_eq!(to_percent16(0x8000), 50);
+        assert_eq!(to_percent16(0xFFFF), 100);
+    }
+
+    #[test]
+    fn test_year_of_date() {
+        assert_eq!(year_of_date(0), 0);
+        assert_eq!(year_of_date(365), 0);
+        assert_eq!(year_of_date(366), 1);
+        assert_eq!(
+            year_of_date(DAYS_TILL_ORIGINAL_BASE_YEAR),
+            ORIGINAL_BASE_YEAR
+        );
+        assert_eq!(year_of_date(730_484), 1999);
+        assert_eq!(year_of_date(730_485), 2000);
     }
 }
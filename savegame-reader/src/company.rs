@@ -0,0 +1,553 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::{has_bit, ORIGINAL_BASE_YEAR, OWNER_NONE, OWNER_TOWN, SLV_31};
+use crate::error::Result;
+use crate::map::{MapTile, TileMap};
+use crate::names::{company_name, president_name};
+use crate::save_file::SaveVersion;
+use crate::table_reader::TableItem;
+use crate::tile::{
+    DiagDirection, RailTileKind, RoadTileKind, StationType, Tile, TransportType, WaterClass,
+    WaterTileKind,
+};
+
+const SLV_ROAD_TYPES: u16 = 214;
+
+const INVALID_ROADTYPE: u8 = 0x3F;
+
+// How many pieces OpenTTD counts for infrastructure that costs more than a plain track
+const LEVEL_CROSSING_TRACKBIT_FACTOR: u32 = 2;
+const ROAD_STOP_TRACKBIT_FACTOR: u32 = 2;
+const ROAD_DEPOT_TRACKBIT_FACTOR: u32 = 2;
+const TUNNELBRIDGE_TRACKBIT_FACTOR: u32 = 4;
+const LOCK_DEPOT_TILE_FACTOR: u32 = 3;
+
+// Road stop graphics from this one on are drive-through stops
+const GFX_TRUCK_BUS_DRIVETHROUGH_OFFSET: u8 = 4;
+
+#[wasm_bindgen(typescript_custom_section)]
+const COMPANY_TS: &'static str = r#"
+export type Colour =
+  | "DarkBlue" | "PaleGreen" | "Pink" | "Yellow" | "Red" | "LightBlue" | "Green" | "DarkGreen"
+  | "Blue" | "Cream" | "Mauve" | "Purple" | "Orange" | "Brown" | "Grey" | "White";
+
+export interface EconomyEntry {
+  income: number;
+  expenses: number;
+  delivered_cargo: number;
+  company_value: number;
+  performance: number;
+}
+
+export interface CompanyInfrastructure {
+  rail: number;
+  signal: number;
+  road: number;
+  tram: number;
+  water: number;
+  station: number;
+  airport: number;
+}
+
+export interface Company {
+  index: number;
+  name: string;
+  president_name: string;
+  is_ai: boolean;
+  colour: Colour;
+  secondary_colour: Colour;
+  money: number;
+  loan: number;
+  inaugurated_year: number;
+  months_of_bankruptcy: number;
+  for_sale: boolean;
+  current_quarter: EconomyEntry;
+  history: EconomyEntry[];
+  infrastructure: CompanyInfrastructure;
+}
+"#;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum Colour {
+    DarkBlue,
+    PaleGreen,
+    Pink,
+    Yellow,
+    Red,
+    LightBlue,
+    Green,
+    DarkGreen,
+    Blue,
+    Cream,
+    Mauve,
+    Purple,
+    Orange,
+    Brown,
+    Grey,
+    White,
+}
+
+const COLOURS: [Colour; 16] = [
+    Colour::DarkBlue,
+    Colour::PaleGreen,
+    Colour::Pink,
+    Colour::Yellow,
+    Colour::Red,
+    Colour::LightBlue,
+    Colour::Green,
+    Colour::DarkGreen,
+    Colour::Blue,
+    Colour::Cream,
+    Colour::Mauve,
+    Colour::Purple,
+    Colour::Orange,
+    Colour::Brown,
+    Colour::Grey,
+    Colour::White,
+];
+
+impl Colour {
+    pub fn from_byte(byte: u8) -> Colour {
+        COLOURS[usize::from(byte & 0x0F)]
+    }
+}
+
+// A quarter of a company's finances, `expenses` being negative like in OpenTTD
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EconomyEntry {
+    pub income: i64,
+    pub expenses: i64,
+    pub delivered_cargo: u32,
+    pub company_value: i64,
+    pub performance: i32,
+}
+
+// Savegames don't store infrastructure counts, OpenTTD counts them from the map after loading.
+// Pieces are counted like it does, so bridges, tunnels and crossings weigh more than a plain
+// track.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct CompanyInfrastructure {
+    pub rail: u32,
+    pub signal: u32,
+    pub road: u32,
+    pub tram: u32,
+    pub water: u32,
+    pub station: u32,
+    pub airport: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Company {
+    pub index: usize,
+    pub name: String,
+    pub president_name: String,
+    pub is_ai: bool,
+    pub colour: Colour,
+    pub secondary_colour: Colour,
+    pub money: i64,
+    pub loan: i64,
+    pub inaugurated_year: i32,
+    pub months_of_bankruptcy: u8,
+    pub for_sale: bool,
+    pub current_quarter: EconomyEntry,
+    // Finished quarters, the most recent one first
+    pub history: Vec<EconomyEntry>,
+    pub infrastructure: CompanyInfrastructure,
+}
+
+// Parts of the savegame a company depends on
+pub struct CompanyContext {
+    pub version: SaveVersion,
+    pub toyland: bool,
+    pub infrastructure: HashMap<u8, CompanyInfrastructure>,
+}
+
+impl Company {
+    pub fn from_item(item: &TableItem, context: &CompanyContext) -> Result<Company> {
+        let colour: u8 = item.field("colour")?;
+        // The default livery holds the company colours
+        let secondary_colour = item
+            .child("liveries")
+            .and_then(|livery| livery.integer("colour2"))
+            .map_or(colour, |colour| colour as u8);
+
+        let mut inaugurated_year: i32 = item.field("inaugurated_year")?;
+        if context.version.is_before(SLV_31) {
+            inaugurated_year += ORIGINAL_BASE_YEAR;
+        }
+
+        Ok(Company {
+            index: item.index,
+            name: company_name(item, context.toyland),
+            president_name: president_name(item, context.toyland),
+            is_ai: item.integer("is_ai").unwrap_or_default() != 0,
+            colour: Colour::from_byte(colour),
+            secondary_colour: Colour::from_byte(secondary_colour),
+            money: item.field("money")?,
+            loan: item.field("current_loan")?,
+            inaugurated_year,
+            months_of_bankruptcy: item.field("months_of_bankruptcy")?,
+            for_sale: item.integer("bankrupt_value").unwrap_or_default() > 0,
+            current_quarter: item
+                .child("cur_economy")
+                .map(EconomyEntry::from_item)
+                .transpose()?
+                .unwrap_or_default(),
+            history: item
+                .structs("old_economy")
+                .map(EconomyEntry::from_item)
+                .collect::<Result<_>>()?,
+            infrastructure: u8::try_from(item.index)
+                .ok()
+                .and_then(|owner| context.infrastructure.get(&owner))
+                .copied()
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl EconomyEntry {
+    fn from_item(item: &TableItem) -> Result<EconomyEntry> {
+        Ok(EconomyEntry {
+            income: item.field("income")?,
+            expenses: item.field("expenses")?,
+            delivered_cargo: item
+                .integers("delivered_cargo")
+                .map(|delivered| delivered as u32)
+                .sum(),
+            company_value: item.field("company_value")?,
+            performance: item.field("performance_history")?,
+        })
+    }
+}
+
+impl CompanyInfrastructure {
+    // Counts the infrastructure of every company on the map, by owner
+    pub fn count(map: &TileMap, version: SaveVersion) -> HashMap<u8, CompanyInfrastructure> {
+        let mut counts: HashMap<u8, CompanyInfrastructure> = HashMap::new();
+        let mut airports = HashSet::new();
+        let mut add = |owner: u8, count: &dyn Fn(&mut CompanyInfrastructure)| {
+            if owner < OWNER_TOWN {
+                count(counts.entry(owner).or_default());
+            }
+        };
+
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let Some(tile) = map.tile(x, y) else {
+                    continue;
+                };
+                let (has_road, has_tram) = road_types(&tile, version);
+                match Tile::from(&tile) {
+                    Tile::Railway { kind, .. } => match kind {
+                        RailTileKind::Normal { track_bits } => {
+                            add(tile.owner, &|c| c.rail += track_pieces(track_bits))
+                        }
+                        RailTileKind::Signals { track_bits } => add(tile.owner, &|c| {
+                            c.rail += track_pieces(track_bits);
+                            c.signal += (tile.m3 >> 4).count_ones();
+                        }),
+                        RailTileKind::Depot { .. } => add(tile.owner, &|c| c.rail += 1),
+                    },
+                    Tile::Road { kind, .. } => match kind {
+                        RoadTileKind::Normal {
+                            road_bits,
+                            tram_bits,
+                        } => {
+                            add(tile.owner, &|c| c.road += road_bits.count_ones());
+                            add(tram_owner(&tile), &|c| c.tram += tram_bits.count_ones());
+                        }
+                        RoadTileKind::Crossing { .. } => {
+                            add(tile.owner, &|c| c.rail += LEVEL_CROSSING_TRACKBIT_FACTOR);
+                            if has_road {
+                                add(road_owner(&tile), &|c| {
+                                    c.road += LEVEL_CROSSING_TRACKBIT_FACTOR
+                                });
+                            }
+                            if has_tram {
+                                add(tram_owner(&tile), &|c| {
+                                    c.tram += LEVEL_CROSSING_TRACKBIT_FACTOR
+                                });
+                            }
+                        }
+                        RoadTileKind::Depot { .. } => add(tile.owner, &|c| {
+                            if has_tram && !has_road {
+                                c.tram += ROAD_DEPOT_TRACKBIT_FACTOR;
+                            } else {
+                                c.road += ROAD_DEPOT_TRACKBIT_FACTOR;
+                            }
+                        }),
+                    },
+                    Tile::Station {
+                        station,
+                        station_type,
+                        water_class,
+                    } => match station_type {
+                        StationType::Airport => {
+                            if airports.insert(station) {
+                                add(tile.owner, &|c| c.airport += 1);
+                            }
+                        }
+                        StationType::Rail | StationType::RailWaypoint => add(tile.owner, &|c| {
+                            c.station += 1;
+                            if !is_station_tile_blocked(&tile) {
+                                c.rail += 1;
+                            }
+                        }),
+                        StationType::Bus | StationType::Truck | StationType::RoadWaypoint => {
+                            add(tile.owner, &|c| c.station += 1);
+                            let drive_through = station_type == StationType::RoadWaypoint
+                                || tile.m5 >= GFX_TRUCK_BUS_DRIVETHROUGH_OFFSET;
+                            let owner = |road_owner| {
+                                if drive_through {
+                                    road_owner
+                                } else {
+                                    tile.owner
+                                }
+                            };
+                            if has_road {
+                                add(owner(road_owner(&tile)), &|c| {
+                                    c.road += ROAD_STOP_TRACKBIT_FACTOR
+                                });
+                            }
+                            if has_tram {
+                                add(owner(tram_owner(&tile)), &|c| {
+                                    c.tram += ROAD_STOP_TRACKBIT_FACTOR
+                                });
+                            }
+                        }
+                        StationType::Dock => add(tile.owner, &|c| {
+                            c.station += 1;
+                            if water_class == WaterClass::Canal {
+                                c.water += 1;
+                            }
+                        }),
+                        StationType::Buoy => {
+                            if water_class == WaterClass::Canal {
+                                add(tile.owner, &|c| c.water += 1);
+                            }
+                        }
+                        StationType::Oilrig => add(tile.owner, &|c| c.station += 1),
+                    },
+                    Tile::Water { water_class, kind } => {
+                        // Locks are owned by their middle part, which isn't a canal itself
+                        let lock_middle = kind == WaterTileKind::Lock && (tile.m5 >> 2) & 3 == 0;
+                        if lock_middle {
+                            add(tile.owner, &|c| c.water += 3 * LOCK_DEPOT_TILE_FACTOR);
+                        } else {
+                            if kind == WaterTileKind::Depot {
+                                add(tile.owner, &|c| c.water += LOCK_DEPOT_TILE_FACTOR);
+                            }
+                            if water_class == WaterClass::Canal {
+                                add(tile.owner, &|c| c.water += 1);
+                            }
+                        }
+                    }
+                    Tile::Object { .. } if WaterClass::of(&tile) == WaterClass::Canal => {
+                        add(tile.owner, &|c| c.water += 1)
+                    }
+                    Tile::TunnelBridge {
+                        transport,
+                        direction,
+                        ..
+                    } => {
+                        // Tunnels and bridges are counted once, from their northern end
+                        if !matches!(
+                            direction,
+                            DiagDirection::SouthWest | DiagDirection::SouthEast
+                        ) {
+                            continue;
+                        }
+                        let Some(length) = tunnel_bridge_length(map, x, y, direction) else {
+                            continue;
+                        };
+                        let pieces = (length + 2) * TUNNELBRIDGE_TRACKBIT_FACTOR;
+                        match transport {
+                            TransportType::Rail => add(tile.owner, &|c| c.rail += pieces),
+                            TransportType::Road => {
+                                if has_road {
+                                    add(road_owner(&tile), &|c| c.road += pieces * 2);
+                                }
+                                if has_tram {
+                                    add(tram_owner(&tile), &|c| c.tram += pieces * 2);
+                                }
+                            }
+                            TransportType::Water => add(tile.owner, &|c| c.water += pieces),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        counts
+    }
+}
+
+// Crossing tracks are counted as the square of their number, like OpenTTD's TracksOverlap()
+fn track_pieces(track_bits: u8) -> u32 {
+    const TRACK_BIT_HORZ: u8 = 0x0C;
+    const TRACK_BIT_VERT: u8 = 0x30;
+    let pieces = track_bits.count_ones();
+    if pieces > 1 && track_bits != TRACK_BIT_HORZ && track_bits != TRACK_BIT_VERT {
+        pieces * pieces
+    } else {
+        pieces
+    }
+}
+
+// Which of road and tram are present on a tile that isn't a plain road. They were flags in m7
+// before road types were introduced.
+fn road_types(tile: &MapTile, version: SaveVersion) -> (bool, bool) {
+    if version.is_before(SLV_ROAD_TYPES) {
+        (
+            has_bit(usize::from(tile.m7), 6),
+            has_bit(usize::from(tile.m7), 7),
+        )
+    } else {
+        (
+            tile.m4 & 0x3F != INVALID_ROADTYPE,
+            (tile.m8 >> 6) as u8 & 0x3F != INVALID_ROADTYPE,
+        )
+    }
+}
+
+// Roads that aren't plain keep their owner apart from the tile's owner
+fn road_owner(tile: &MapTile) -> u8 {
+    tile.m7 & 0x1F
+}
+
+// Trams don't need OWNER_TOWN, which stands for OWNER_NONE instead
+fn tram_owner(tile: &MapTile) -> u8 {
+    match tile.m3 >> 4 {
+        OWNER_TOWN => OWNER_NONE,
+        owner => owner,
+    }
+}
+
+// OpenTTD's IsStationTileBlocked(). Only tiles of NewGRF stations, which have a custom station
+// spec index in m4, can be blocked. Which of their tiles are is defined by the GRF and not stored
+// in the savegame, so they're taken to be unblocked like most NewGRF station tiles are.
+fn is_station_tile_blocked(_tile: &MapTile) -> bool {
+    false
+}
+
+// The height of the lowest corner of a tile like OpenTTD's GetTileZ(), a tile's own height being
+// that of its north corner
+fn tile_z(map: &TileMap, x: u32, y: u32) -> Option<u8> {
+    [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)]
+        .into_iter()
+        .filter_map(|(x, y)| map.tile(x, y))
+        .map(|tile| tile.height)
+        .min()
+}
+
+// The number of tiles between the two ends of a tunnel or bridge
+fn tunnel_bridge_length(map: &TileMap, x: u32, y: u32, direction: DiagDirection) -> Option<u32> {
+    let (dx, dy): (i64, i64) = match direction {
+        DiagDirection::NorthEast => (-1, 0),
+        DiagDirection::SouthEast => (0, 1),
+        DiagDirection::SouthWest => (1, 0),
+        DiagDirection::NorthWest => (0, -1),
+    };
+    let z = tile_z(map, x, y)?;
+    let is_bridge =
+        matches!(map.decode_tile(x, y), Some(Tile::TunnelBridge { is_bridge, .. }) if is_bridge);
+    let opposite = DiagDirection::from_bits(direction as u8 + 2);
+
+    let (mut x, mut y) = (i64::from(x), i64::from(y));
+    for length in 0.. {
+        x += dx;
+        y += dy;
+        let tile = map.tile(u32::try_from(x).ok()?, u32::try_from(y).ok()?)?;
+        if let Tile::TunnelBridge {
+            direction,
+            is_bridge: end_is_bridge,
+            ..
+        } = Tile::from(&tile)
+        {
+            // Tunnels end at the same height, bridges at the next bridge head
+            if direction == opposite
+                && end_is_bridge == is_bridge
+                && (is_bridge || tile_z(map, x as u32, y as u32) == Some(z))
+            {
+                return Some(length);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::company::tunnel_bridge_length;
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::tile::{DiagDirection, Tile};
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_companies_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let companies = save_game.companies().unwrap();
+        assert_eq!(companies.len(), 3);
+        assert_eq!(companies[0].name, "Murphy & Co.");
+        assert!(companies.iter().all(|company| company.history.len() <= 24));
+        let infrastructure = companies[0].infrastructure;
+        assert!(infrastructure.station > 0 && infrastructure.airport > 0);
+    }
+
+    #[test]
+    fn test_companies_from_tables() {
+        let save_game = load("./test-big.sav");
+        let companies = save_game.companies().unwrap();
+        let company = &companies[0];
+        assert_eq!(company.name, "Haessler Transport");
+        assert_eq!(company.inaugurated_year, 1950);
+        assert_eq!(company.history.len(), 24);
+        assert!(company.infrastructure.rail > 0 && company.infrastructure.signal > 0);
+    }
+
+    #[test]
+    fn test_tunnel_lengths() {
+        let save_game = load("./test_v196.sav");
+        let map = save_game.tile_map().unwrap();
+        let mut tunnels = vec![];
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                if let Some(Tile::TunnelBridge {
+                    is_bridge: false,
+                    direction,
+                    ..
+                }) = map.decode_tile(x, y)
+                {
+                    tunnels.push((x, y, tunnel_bridge_length(map, x, y, direction)));
+                }
+            }
+        }
+        // Both portals of every tunnel find each other, even though they slope in opposite
+        // directions
+        assert_eq!(tunnels.len(), 70);
+        assert!(tunnels.iter().all(|(_, _, length)| length.is_some()));
+        assert_eq!(
+            tunnel_bridge_length(map, 575, 243, DiagDirection::SouthEast),
+            Some(41)
+        );
+        assert_eq!(
+            tunnel_bridge_length(map, 575, 243 + 42, DiagDirection::NorthWest),
+            Some(41)
+        );
+        assert_eq!(
+            tunnel_bridge_length(map, 932, 802, DiagDirection::SouthEast),
+            Some(6)
+        );
+    }
+}
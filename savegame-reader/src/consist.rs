@@ -0,0 +1,334 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::has_bit;
+use crate::engine::Engine;
+use crate::vehicle::{Vehicle, VehicleType};
+
+// GroundVehicleSubtypeFlags, which trains and road vehicles share
+const GVSF_FRONT: u8 = 0;
+const GVSF_ARTICULATED_PART: u8 = 1;
+const GVSF_ENGINE: u8 = 3;
+const GVSF_MULTIHEADED: u8 = 5;
+
+// The length of a vehicle part that isn't shortened, in sixteenths of a tile
+const VEHICLE_LENGTH: u32 = 8;
+
+#[wasm_bindgen(typescript_custom_section)]
+const CONSIST_TS: &'static str = r#"
+export interface ConsistCargo {
+  cargo: number;
+  capacity: number;
+  count: number;
+}
+
+export interface ConsistPart {
+  index: number;
+  engine: number;
+  power?: number;
+}
+
+export interface Consist {
+  index: number;
+  vehicle_type: VehicleType;
+  owner: number;
+  unit_number?: number;
+  name?: string;
+  engine: number;
+  parts: ConsistPart[];
+  engines: number;
+  power?: number;
+  length?: number;
+  cargo: ConsistCargo[];
+  tile: number;
+  x: number;
+  y: number;
+  hidden: boolean;
+  stopped: boolean;
+  crashed: boolean;
+  age: number;
+  reliability: number;
+  profit_this_year: number;
+  profit_last_year: number;
+  value: number;
+}
+"#;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsistCargo {
+    pub cargo: u8,
+    pub capacity: u32,
+    pub count: u32,
+}
+
+// A vehicle part's share of the power in hp. Power isn't stored in savegames, OpenTTD takes it from
+// the engine specs, so it's only known for original engines. Like in the game, wagons,
+// articulated parts and road vehicle trailers have none and each head of a multiheaded engine
+// has half of it. Electric engines on unelectrified rail aren't accounted for.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsistPart {
+    pub index: usize,
+    pub engine: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<u32>,
+}
+
+// A whole vehicle as the game lists it, made up of the parts its front part links to. Details
+// that apply to the whole vehicle are taken from the front part, value is summed over all parts.
+// `power` and `length` are only known if every part is an original engine, as NewGRFs can change
+// both through callbacks.
+#[derive(Debug, Clone, Serialize)]
+pub struct Consist {
+    pub index: usize,
+    pub vehicle_type: VehicleType,
+    pub owner: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_number: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub engine: u16,
+    pub parts: Vec<ConsistPart>,
+    pub engines: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<u32>,
+    // In sixteenths of a tile, only for trains and road vehicles
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<u32>,
+    pub cargo: Vec<ConsistCargo>,
+    pub tile: u32,
+    pub x: i64,
+    pub y: i64,
+    pub hidden: bool,
+    pub stopped: bool,
+    pub crashed: bool,
+    pub age: i32,
+    pub reliability: u16,
+    pub profit_this_year: i64,
+    pub profit_last_year: i64,
+    pub value: i64,
+}
+
+impl Consist {
+    // How full the vehicle is over all its cargos, from 0 to 100
+    pub fn load_percent(&self) -> u8 {
+        let capacity: u32 = self.cargo.iter().map(|cargo| cargo.capacity).sum();
+        let count: u32 = self.cargo.iter().map(|cargo| cargo.count).sum();
+        match capacity {
+            0 => 0,
+            capacity => (count.min(capacity) * 100 / capacity) as u8,
+        }
+    }
+}
+
+// Groups vehicle parts into the vehicles they make up. Wagons that aren't attached to an engine,
+// effect vehicles and disasters are left out, like in the game's vehicle lists.
+pub fn build_consists(vehicles: &[Vehicle], engines: &[Engine]) -> Vec<Consist> {
+    let by_index: HashMap<usize, &Vehicle> = vehicles
+        .iter()
+        .map(|vehicle| (vehicle.index, vehicle))
+        .collect();
+    let linked: HashSet<usize> = vehicles.iter().filter_map(|vehicle| vehicle.next).collect();
+
+    vehicles
+        .iter()
+        .filter(|vehicle| !linked.contains(&vehicle.index))
+        .filter_map(|front| {
+            let details = front.details.as_ref()?;
+            let ground_vehicle =
+                matches!(front.vehicle_type, VehicleType::Train | VehicleType::Road);
+            if ground_vehicle && !has_bit(usize::from(front.subtype), GVSF_FRONT) {
+                return None;
+            }
+
+            let mut parts = vec![front];
+            let mut visited = HashSet::from([front.index]);
+            while let Some(next) = parts
+                .last()
+                .and_then(|part| part.next)
+                .and_then(|next| by_index.get(&next))
+            {
+                // Broken savegames could link parts in a loop
+                if !visited.insert(next.index) {
+                    break;
+                }
+                parts.push(next);
+            }
+
+            let mut cargo: BTreeMap<u8, ConsistCargo> = BTreeMap::new();
+            for details in parts.iter().filter_map(|part| part.details.as_ref()) {
+                if details.cargo_capacity == 0 && details.cargo_count == 0 {
+                    continue;
+                }
+                let entry = cargo
+                    .entry(details.cargo_type)
+                    .or_insert_with(|| ConsistCargo {
+                        cargo: details.cargo_type,
+                        capacity: 0,
+                        count: 0,
+                    });
+                entry.capacity += u32::from(details.cargo_capacity);
+                entry.count += details.cargo_count;
+            }
+
+            let consist_parts: Vec<ConsistPart> = parts
+                .iter()
+                .filter_map(|part| {
+                    let engine = part.details.as_ref()?.engine;
+                    Some(ConsistPart {
+                        index: part.index,
+                        engine,
+                        power: part_power(part, engines.get(usize::from(engine))),
+                    })
+                })
+                .collect();
+            // Parts of original engines are never shortened
+            let original = consist_parts.iter().all(|part| {
+                engines
+                    .get(usize::from(part.engine))
+                    .is_some_and(|engine| engine.grfid.is_none())
+            });
+
+            let powered = match front.vehicle_type {
+                VehicleType::Train => parts
+                    .iter()
+                    .filter(|part| has_bit(usize::from(part.subtype), GVSF_ENGINE))
+                    .count() as u32,
+                _ => 1,
+            };
+
+            Some(Consist {
+                index: front.index,
+                vehicle_type: front.vehicle_type,
+                owner: front.owner?,
+                unit_number: details.unit_number,
+                name: details.name.clone(),
+                engine: details.engine,
+                power: consist_parts.iter().map(|part| part.power).sum(),
+                parts: consist_parts,
+                engines: powered,
+                length: (ground_vehicle && original).then(|| parts.len() as u32 * VEHICLE_LENGTH),
+                cargo: cargo.into_values().collect(),
+                tile: front.tile,
+                x: front.x,
+                y: front.y,
+                hidden: front.hidden,
+                stopped: details.stopped,
+                crashed: details.crashed,
+                age: details.age,
+                reliability: details.reliability,
+                profit_this_year: details.profit_this_year,
+                profit_last_year: details.profit_last_year,
+                value: parts
+                    .iter()
+                    .filter_map(|part| part.details.as_ref())
+                    .map(|details| details.value)
+                    .sum(),
+            })
+        })
+        .collect()
+}
+
+// A part's share of its engine's power, like OpenTTD's GetPower()
+fn part_power(part: &Vehicle, engine: Option<&Engine>) -> Option<u32> {
+    let subtype = usize::from(part.subtype);
+    let ground_vehicle = matches!(part.vehicle_type, VehicleType::Train | VehicleType::Road);
+    if ground_vehicle && has_bit(subtype, GVSF_ARTICULATED_PART) {
+        return Some(0);
+    }
+    if part.vehicle_type == VehicleType::Road && !has_bit(subtype, GVSF_FRONT) {
+        return Some(0);
+    }
+    let power = u32::from(engine?.power?);
+    if part.vehicle_type == VehicleType::Train && has_bit(subtype, GVSF_MULTIHEADED) {
+        return Some(power / 2);
+    }
+    Some(power)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::vehicle::VehicleType;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_consists() {
+        let save_game = load("./test-big.sav");
+        let vehicles = save_game.vehicles().unwrap();
+        let consists = save_game.consists().unwrap();
+
+        // Every vehicle with a unit number is listed exactly once
+        let numbered = vehicles
+            .iter()
+            .filter(|vehicle| {
+                vehicle
+                    .details
+                    .as_ref()
+                    .is_some_and(|details| details.unit_number.is_some())
+            })
+            .count();
+        assert_eq!(consists.len(), numbered);
+        assert!(consists
+            .iter()
+            .filter(|consist| consist.vehicle_type == VehicleType::Train)
+            .all(|consist| consist.engines > 0 && consist.parts.len() > 1));
+        assert!(consists.iter().all(|consist| consist.load_percent() <= 100));
+
+        // All trains use NewGRF engines, whose power and length depend on the NewGRF
+        assert!(consists
+            .iter()
+            .filter(|consist| consist.vehicle_type == VehicleType::Train)
+            .all(|consist| consist.power.is_none() && consist.length.is_none()));
+    }
+
+    #[test]
+    fn test_consists_of_original_engines() {
+        let save_game = load("./test_v196.sav");
+        let consists = save_game.consists().unwrap();
+        let trains: Vec<_> = consists
+            .iter()
+            .filter(|consist| consist.vehicle_type == VehicleType::Train)
+            .collect();
+        assert!(!trains.is_empty());
+        for train in &trains {
+            assert_eq!(train.length, Some(train.parts.len() as u32 * 8));
+            assert!(train.power.is_some_and(|power| power > 0));
+            // Original wagons come after the 27 engines of the rail set
+            assert!(train
+                .parts
+                .iter()
+                .filter(|part| part.engine >= 27)
+                .all(|part| part.power == Some(0)));
+        }
+
+        // Both heads of a multiheaded engine get half of its power
+        let multiheaded = trains.iter().find(|train| train.engine == 20).unwrap();
+        let heads: Vec<_> = multiheaded
+            .parts
+            .iter()
+            .filter(|part| part.engine == 20)
+            .collect();
+        assert_eq!(heads.len(), 2);
+        assert!(heads.iter().all(|head| head.power == Some(2000)));
+        assert_eq!(multiheaded.power, Some(4000));
+
+        // Only the front of a road vehicle or ship has an engine, ships have no power in the game
+        assert!(consists
+            .iter()
+            .filter(|consist| consist.vehicle_type == VehicleType::Road)
+            .all(|consist| consist.power.is_some_and(|power| power > 0)));
+        assert!(consists
+            .iter()
+            .filter(|consist| consist.vehicle_type == VehicleType::Ship)
+            .all(|consist| consist.power.is_none() && consist.length.is_none()));
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::{has_bit, to_percent16, year_of_date, DAYS_TILL_ORIGINAL_BASE_YEAR, SLV_31};
+use crate::error::Result;
+use crate::save_file::SaveVersion;
+use crate::table_reader::TableItem;
+use crate::vehicle::VehicleType;
+
+// EngineFlags
+const ENGINE_AVAILABLE: u8 = 0;
+const ENGINE_EXCLUSIVE_PREVIEW: u8 = 1;
+
+const MAX_COMPANIES: u8 = 15;
+const INVALID_COMPANY: u8 = 0xFF;
+// Engines of the base game are mapped to no NewGRF
+const INVALID_GRFID: u32 = 0xFFFF_FFFF;
+
+// Original engines are numbered trains first, then road vehicles, ships and aircraft
+const NUM_ORIGINAL_TRAINS: usize = 116;
+const NUM_ORIGINAL_ROAD_VEHICLES: usize = 88;
+
+// The power in hp of the original trains, from OpenTTD's engine table. The rail, monorail and
+// maglev sets each list their engines (27, 3 and 5 of them) before their 27 wagons, which have
+// no power.
+const ORIGINAL_TRAIN_POWERS: [u16; NUM_ORIGINAL_TRAINS] = [
+    300, 1000, 400, 1000, 2000, 1400, 2500, 1200, 1050, 1800, 2000, 1400, 2000, 1250, 1750, 2580,
+    4000, 2400, 6600, 3100, 4000, 3300, 4500, 5000, 6000, 8000, 10000, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4000, 6000, 3000, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6000, 8000, 10000, 12000, 5000, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+// The power in hp of the original road vehicles: the seven buses, then the lorries of each cargo
+// from the oldest to the newest model
+const ORIGINAL_ROAD_VEHICLE_POWERS: [u16; NUM_ORIGINAL_ROAD_VEHICLES] = [
+    90, 150, 200, 260, 90, 150, 200, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90,
+    120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150,
+    90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120,
+    150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90, 120, 150, 90,
+    120, 150, 90, 120, 150, 90, 120, 150,
+];
+
+#[wasm_bindgen(typescript_custom_section)]
+const ENGINE_TS: &'static str = r#"
+export interface Engine {
+  index: number;
+  vehicle_type?: VehicleType;
+  grfid?: number;
+  internal_id?: number;
+  name?: string;
+  power?: number;
+  intro_date: number;
+  intro_year: number;
+  age: number;
+  reliability: number;
+  reliability_max: number;
+  reliability_final: number;
+  duration_phase_1: number;
+  duration_phase_2: number;
+  duration_phase_3: number;
+  available: boolean;
+  exclusive_preview: boolean;
+  preview_company?: number;
+  companies: number[];
+  hidden_for: number[];
+}
+"#;
+
+// A vehicle model, which vehicles refer to by their `engine`. The game takes its name from the
+// original vehicles or the NewGRF it is mapped to, so only custom names are known.
+//
+// An engine is introduced at `intro_date`, with its reliability rising to `reliability_max` over
+// the first phase, staying there during the second phase and dropping to `reliability_final`
+// over the third one. Unless vehicles never expire, it can't be bought anymore after that.
+#[derive(Debug, Clone, Serialize)]
+pub struct Engine {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vehicle_type: Option<VehicleType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grfid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_id: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    // In hp, only known for the original trains and road vehicles
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<u16>,
+    pub intro_date: i32,
+    pub intro_year: i32,
+    // Months since the introduction
+    pub age: i32,
+    pub reliability: u16,
+    pub reliability_max: u16,
+    pub reliability_final: u16,
+    // The length of each phase in months
+    pub duration_phase_1: u16,
+    pub duration_phase_2: u16,
+    pub duration_phase_3: u16,
+    pub available: bool,
+    pub exclusive_preview: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview_company: Option<u8>,
+    // The companies that can buy the engine and those that hid it from their purchase lists
+    pub companies: Vec<u8>,
+    pub hidden_for: Vec<u8>,
+}
+
+// Parts of other chunks an engine refers to
+pub struct EngineContext<'a> {
+    pub version: SaveVersion,
+    pub engine_mapping: HashMap<usize, &'a TableItem>,
+}
+
+impl Engine {
+    pub fn from_item(item: &TableItem, context: &EngineContext) -> Result<Engine> {
+        let mut intro_date: i32 = item.field("intro_date")?;
+        if context.version.is_before(SLV_31) {
+            intro_date += DAYS_TILL_ORIGINAL_BASE_YEAR;
+        }
+        let flags: u8 = item.field("flags")?;
+        let mapping = context.engine_mapping.get(&item.index);
+        let grfid = mapping
+            .and_then(|mapping| mapping.integer("grfid"))
+            .map(|grfid| grfid as u32)
+            .filter(|grfid| *grfid != 0 && *grfid != INVALID_GRFID);
+
+        Ok(Engine {
+            index: item.index,
+            vehicle_type: mapping
+                .and_then(|mapping| mapping.integer("type"))
+                .and_then(|byte| VehicleType::from_byte(byte as u8)),
+            grfid,
+            internal_id: mapping
+                .filter(|_| grfid.is_some())
+                .and_then(|mapping| mapping.integer("internal_id"))
+                .map(|id| id as u16),
+            name: item.string("name").map(String::from),
+            power: original_power(item.index).filter(|_| grfid.is_none()),
+            intro_date,
+            intro_year: year_of_date(intro_date),
+            age: item.field("age")?,
+            reliability: item.field("reliability")?,
+            reliability_max: item.field("reliability_max")?,
+            reliability_final: item.field("reliability_final")?,
+            duration_phase_1: item.field("duration_phase_1")?,
+            duration_phase_2: item.field("duration_phase_2")?,
+            duration_phase_3: item.field("duration_phase_3")?,
+            available: has_bit(usize::from(flags), ENGINE_AVAILABLE),
+            exclusive_preview: has_bit(usize::from(flags), ENGINE_EXCLUSIVE_PREVIEW),
+            preview_company: item
+                .integer("preview_company")
+                .map(|company| company as u8)
+                .filter(|company| *company != INVALID_COMPANY),
+            companies: companies(item.integer("company_avail").unwrap_or_default()),
+            hidden_for: companies(item.integer("company_hidden").unwrap_or_default()),
+        })
+    }
+
+    // Months from the introduction until the engine is at its final reliability
+    pub fn lifetime(&self) -> u32 {
+        u32::from(self.duration_phase_1)
+            + u32::from(self.duration_phase_2)
+            + u32::from(self.duration_phase_3)
+    }
+
+    pub fn reliability_percent(&self) -> u8 {
+        to_percent16(self.reliability)
+    }
+}
+
+fn original_power(index: usize) -> Option<u16> {
+    match index.checked_sub(NUM_ORIGINAL_TRAINS) {
+        None => Some(ORIGINAL_TRAIN_POWERS[index]),
+        Some(road_index) => ORIGINAL_ROAD_VEHICLE_POWERS.get(road_index).copied(),
+    }
+}
+
+fn companies(mask: i64) -> Vec<u8> {
+    (0..MAX_COMPANIES)
+        .filter(|company| has_bit(mask as usize, *company))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::vehicle::VehicleType;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_engines_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let engines = save_game.engines().unwrap();
+        assert_eq!(engines.len(), 256);
+        // Without NewGRFs all engines are the original ones
+        assert!(engines.iter().all(|engine| engine.grfid.is_none()));
+        assert!(engines
+            .iter()
+            .all(|engine| engine.intro_year >= 1920 && engine.vehicle_type.is_some()));
+        assert!(engines
+            .iter()
+            .all(|engine| engine.reliability_percent() <= 100));
+        // Power is known for the original trains and road vehicles, ships and aircraft have none
+        assert!(engines.iter().all(|engine| engine.power.is_some()
+            == matches!(
+                engine.vehicle_type,
+                Some(VehicleType::Train | VehicleType::Road)
+            )));
+
+        // Every vehicle is of an engine its company was able to buy
+        let vehicles = save_game.vehicles().unwrap();
+        assert!(vehicles.iter().all(|vehicle| {
+            let (Some(owner), Some(details)) = (vehicle.owner, &vehicle.details) else {
+                return true;
+            };
+            engines
+                .get(usize::from(details.engine))
+                .is_some_and(|engine| engine.available && engine.companies.contains(&owner))
+        }));
+    }
+
+    #[test]
+    fn test_engines_from_tables() {
+        let save_game = load("./test-big.sav");
+        let engines = save_game.engines().unwrap();
+        assert_eq!(engines.len(), 351);
+        assert!(engines.iter().any(|engine| engine.grfid.is_some()));
+        assert!(engines
+            .iter()
+            .filter(|engine| engine.grfid.is_some())
+            .all(|engine| engine.power.is_none()));
+        assert!(engines
+            .iter()
+            .any(|engine| engine.vehicle_type == Some(VehicleType::Ship)));
+        assert!(engines
+            .iter()
+            .filter(|engine| engine.available)
+            .all(|engine| engine.age >= 0));
+        assert!(engines.iter().any(|engine| !engine.companies.is_empty()));
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+use std::io;
+
+pub type Result<T> = std::result::Result<T, SaveGameError>;
+
+#[derive(Debug)]
+pub enum SaveGameError {
+    UnknownFormat([u8; 4]),
+    Decompression(String),
+    UnsupportedChunkType {
+        chunk_type: u8,
+        offset: u64,
+    },
+    UnknownFieldType {
+        field_type: u8,
+        offset: u64,
+    },
+    BadGamma {
+        offset: u64,
+    },
+    InvalidUtf8 {
+        offset: u64,
+    },
+    Truncated {
+        offset: u64,
+    },
+    Corrupt {
+        offset: u64,
+        reason: String,
+    },
+    MissingChunk(String),
+    InvalidField(String),
+    InChunk {
+        chunk_id: String,
+        offset: u64,
+        source: Box<SaveGameError>,
+    },
+    Io(io::Error),
+}
+
+impl SaveGameError {
+    /// Attaches the chunk that was being read to an error. `position` is the offset the reader
+    /// had reached when the error surfaced and is used for I/O errors that don't carry one.
+    pub fn in_chunk(self, chunk_id: &str, chunk_offset: u64, position: u64) -> SaveGameError {
+        SaveGameError::InChunk {
+            chunk_id: String::from(chunk_id),
+            offset: chunk_offset,
+            source: Box::new(self.at_position(position)),
+        }
+    }
+
+    /// Turns a premature end of the stream into a `Truncated` error at `position`.
+    pub fn at_position(self, position: u64) -> SaveGameError {
+        match self {
+            SaveGameError::Io(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                SaveGameError::Truncated { offset: position }
+            }
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for SaveGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveGameError::UnknownFormat(tag) => {
+                write!(f, "unknown savegame format '{}'", tag.escape_ascii())
+            }
+            SaveGameError::Decompression(reason) => write!(f, "decompression failed: {}", reason),
+            SaveGameError::UnsupportedChunkType { chunk_type, offset } => write!(
+                f,
+                "unsupported chunk type {} at byte {}",
+                chunk_type, offset
+            ),
+            SaveGameError::UnknownFieldType { field_type, offset } => {
+                write!(f, "unknown field type {} at byte {}", field_type, offset)
+            }
+            SaveGameError::BadGamma { offset } => {
+                write!(f, "invalid gamma encoded length at byte {}", offset)
+            }
+            SaveGameError::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 in string at byte {}", offset)
+            }
+            SaveGameError::Truncated { offset } => {
+                write!(f, "unexpected end of savegame at byte {}", offset)
+            }
+            SaveGameError::Corrupt { offset, reason } => {
+                write!(f, "corrupt savegame at byte {}: {}", offset, reason)
+            }
+            SaveGameError::MissingChunk(chunk_id) => {
+                write!(f, "savegame contains no '{}' chunk", chunk_id)
+            }
+            SaveGameError::InvalidField(key) => write!(f, "missing or invalid field '{}'", key),
+            SaveGameError::InChunk {
+                chunk_id,
+                offset,
+                source,
+            } => write!(
+                f,
+                "in chunk '{}' starting at byte {}: {}",
+                chunk_id, offset, source
+            ),
+            SaveGameError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SaveGameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveGameError::InChunk { source, .. } => Some(source.as_ref()),
+            SaveGameError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveGameError {
+    fn from(err: io::Error) -> Self {
+        // The streaming decompressors report malformed input as invalid data
+        if err.kind() == io::ErrorKind::InvalidData {
+            return SaveGameError::Decompression(err.to_string());
+        }
+        SaveGameError::Io(err)
+    }
+}
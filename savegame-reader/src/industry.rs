@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::to_percent8;
+use crate::error::{Result, SaveGameError};
+use crate::save_file::SaveVersion;
+use crate::table_reader::TableItem;
+
+const SLV_EXTEND_INDUSTRY_CARGO_SLOTS: u16 = 202;
+
+// Industry types from this one on are defined by NewGRFs
+const NEW_INDUSTRYOFFSET: u8 = 37;
+const CT_INVALID: u8 = 0xFF;
+
+#[wasm_bindgen(typescript_custom_section)]
+const INDUSTRY_TS: &'static str = r#"
+export interface IndustryType {
+  id: number;
+  name?: string;
+  grfid?: number;
+  grf_local_id?: number;
+}
+
+export interface ProducedCargo {
+  cargo: number;
+  waiting: number;
+  rate: number;
+  last_month_production: number;
+  last_month_transported: number;
+  last_month_pct_transported: number;
+}
+
+export interface AcceptedCargo {
+  cargo: number;
+  waiting: number;
+  last_accepted: number;
+}
+
+export interface Industry {
+  index: number;
+  industry_type: IndustryType;
+  xy: number;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  town: number;
+  owner: number;
+  produced: ProducedCargo[];
+  accepted: AcceptedCargo[];
+}
+"#;
+
+// The original industry types in the order of OpenTTD's IndustryTypes
+const INDUSTRY_NAMES: [&str; NEW_INDUSTRYOFFSET as usize] = [
+    "Coal Mine",
+    "Power Station",
+    "Sawmill",
+    "Forest",
+    "Oil Refinery",
+    "Oil Rig",
+    "Factory",
+    "Printing Works",
+    "Steel Mill",
+    "Farm",
+    "Copper Ore Mine",
+    "Oil Wells",
+    "Bank",
+    "Food Processing Plant",
+    "Paper Mill",
+    "Gold Mine",
+    "Bank",
+    "Diamond Mine",
+    "Iron Ore Mine",
+    "Fruit Plantation",
+    "Rubber Plantation",
+    "Water Supply",
+    "Water Tower",
+    "Factory",
+    "Farm",
+    "Lumber Mill",
+    "Candyfloss Forest",
+    "Sweet Factory",
+    "Battery Farm",
+    "Cola Wells",
+    "Toy Shop",
+    "Toy Factory",
+    "Plastic Fountains",
+    "Fizzy Drink Factory",
+    "Bubble Generator",
+    "Toffee Quarry",
+    "Sugar Mine",
+];
+
+// Original industry types have a name, NewGRF ones are identified by their GRF and the id the
+// GRF gave them instead, which IIDS maps the type to
+#[derive(Debug, Clone, Serialize)]
+pub struct IndustryType {
+    pub id: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grfid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grf_local_id: Option<u8>,
+}
+
+// `last_month_pct_transported` is the percentage the game shows, converted from the fraction of
+// 255 OpenTTD stores
+#[derive(Debug, Clone, Serialize)]
+pub struct ProducedCargo {
+    pub cargo: u8,
+    pub waiting: u16,
+    pub rate: u8,
+    pub last_month_production: u16,
+    pub last_month_transported: u16,
+    pub last_month_pct_transported: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AcceptedCargo {
+    pub cargo: u8,
+    pub waiting: u16,
+    // The date cargo was last delivered, 0 if it never was
+    pub last_accepted: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Industry {
+    pub index: usize,
+    pub industry_type: IndustryType,
+    pub xy: u32,
+    pub x: u32,
+    pub y: u32,
+    pub width: u8,
+    pub height: u8,
+    pub town: usize,
+    pub owner: u8,
+    pub produced: Vec<ProducedCargo>,
+    pub accepted: Vec<AcceptedCargo>,
+}
+
+// Parts of other chunks an industry refers to
+pub struct IndustryContext<'a> {
+    pub version: SaveVersion,
+    pub map_width: u32,
+    pub industry_mapping: HashMap<usize, &'a TableItem>,
+}
+
+impl IndustryType {
+    fn resolve(id: u8, mapping: &HashMap<usize, &TableItem>) -> IndustryType {
+        let grf = mapping
+            .get(&usize::from(id))
+            .and_then(|entity| Some((entity.field("grfid").ok()?, entity.field("entity_id").ok()?)))
+            .filter(|(grfid, _)| *grfid != 0);
+        IndustryType {
+            id,
+            name: INDUSTRY_NAMES
+                .get(usize::from(id))
+                .filter(|_| grf.is_none())
+                .map(|name| String::from(*name)),
+            grfid: grf.map(|(grfid, _)| grfid),
+            grf_local_id: grf.map(|(_, local_id)| local_id),
+        }
+    }
+}
+
+impl Industry {
+    pub fn from_item(item: &TableItem, context: &IndustryContext) -> Result<Industry> {
+        let xy: u32 = item.field("location.tile")?;
+        let list = |key| item.integers(key).collect::<Vec<_>>();
+        let value = |values: &Vec<i64>, slot: usize| values.get(slot).copied().unwrap_or_default();
+
+        let production = [
+            "produced_cargo_waiting",
+            "production_rate",
+            "last_month_production",
+            "last_month_transported",
+            "last_month_pct_transported",
+        ]
+        .map(list);
+        let produced = list("produced_cargo")
+            .into_iter()
+            .enumerate()
+            .filter(|(_, cargo)| *cargo != i64::from(CT_INVALID))
+            .map(|(slot, cargo)| ProducedCargo {
+                cargo: cargo as u8,
+                waiting: value(&production[0], slot) as u16,
+                rate: value(&production[1], slot) as u8,
+                last_month_production: value(&production[2], slot) as u16,
+                last_month_transported: value(&production[3], slot) as u16,
+                last_month_pct_transported: to_percent8(value(&production[4], slot) as u8),
+            })
+            .collect();
+
+        let waiting = list("incoming_cargo_waiting");
+        let mut last_accepted = list("last_cargo_accepted_at");
+        // Older savegames only kept a single date for all accepted cargos
+        if context.version.is_before(SLV_EXTEND_INDUSTRY_CARGO_SLOTS) {
+            last_accepted = vec![value(&last_accepted, 0); waiting.len()];
+        }
+        let accepted = list("accepts_cargo")
+            .into_iter()
+            .enumerate()
+            .filter(|(_, cargo)| *cargo != i64::from(CT_INVALID))
+            .map(|(slot, cargo)| AcceptedCargo {
+                cargo: cargo as u8,
+                waiting: value(&waiting, slot) as u16,
+                last_accepted: value(&last_accepted, slot) as i32,
+            })
+            .collect();
+
+        Ok(Industry {
+            index: item.index,
+            industry_type: IndustryType::resolve(item.field("type")?, &context.industry_mapping),
+            xy,
+            x: xy % context.map_width,
+            y: xy / context.map_width,
+            width: item.field("location.w")?,
+            height: item.field("location.h")?,
+            town: item
+                .reference("town")
+                .ok_or(SaveGameError::InvalidField(String::from("town")))?,
+            owner: item.field("owner")?,
+            produced,
+            accepted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_industries_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let industries = save_game.industries().unwrap();
+        assert_eq!(industries.len(), 744);
+        assert!(industries
+            .iter()
+            .all(|industry| industry.industry_type.name.is_some()));
+        let cargos = save_game.cargo_table();
+        assert!(industries
+            .iter()
+            .flat_map(|industry| &industry.produced)
+            .all(|produced| cargos.get(produced.cargo).is_some()));
+        let towns = save_game.indexed_items("CITY");
+        assert!(industries
+            .iter()
+            .all(|industry| towns.contains_key(&industry.town)));
+    }
+
+    #[test]
+    fn test_industries_from_tables() {
+        let save_game = load("./test-big.sav");
+        let (width, height) = save_game.map_size().unwrap();
+        let industries = save_game.industries().unwrap();
+        assert!(industries
+            .iter()
+            .all(|industry| industry.x < width && industry.y < height));
+        assert!(industries
+            .iter()
+            .flat_map(|industry| &industry.produced)
+            .any(|produced| produced.last_month_production > 0));
+        assert!(industries
+            .iter()
+            .any(|industry| !industry.accepted.is_empty()));
+        // The best served cargo had 204/255 of its production transported, which the game shows
+        // as 80%
+        let best = industries
+            .iter()
+            .flat_map(|industry| &industry.produced)
+            .max_by_key(|produced| produced.last_month_pct_transported)
+            .unwrap();
+        assert_eq!(best.last_month_pct_transported, 80);
+    }
+}
@@ -1,25 +1,139 @@
 use std::io::Cursor;
 
-use wasm_bindgen::prelude::wasm_bindgen;
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::{wasm_bindgen, JsError, JsValue};
 
+use crate::minimap::{Minimap, MinimapMode};
 use crate::save_file::CompressedSaveFile;
-use crate::table_reader::TableItem;
+use crate::save_game::SaveGame;
 
+pub mod cargo;
 mod common;
-mod loader;
-mod save_file;
-mod table_reader;
-
-#[wasm_bindgen]
-pub fn load_file(buffer: &[u8]) -> String {
-    console_error_panic_hook::set_once();
-
-    let file = CompressedSaveFile::new(Cursor::new(buffer));
-    let chunks = loader::load_file(file).unwrap();
-    let relevant_chunk_part = chunks
-        .get("LGRP")
-        .unwrap()
-        .iter()
-        .collect::<Vec<&TableItem>>();
-    serde_json::to_string(relevant_chunk_part.as_slice()).unwrap()
+pub mod company;
+pub mod consist;
+pub mod engine;
+pub mod error;
+pub mod industry;
+pub mod line;
+pub mod link_graph;
+pub mod loader;
+pub mod map;
+pub mod minimap;
+pub mod names;
+pub mod newgrf;
+pub mod order;
+pub mod save_file;
+pub mod save_game;
+mod schema;
+pub mod station;
+pub mod table_reader;
+pub mod tile;
+pub mod town;
+pub mod town_names;
+pub mod vehicle;
+
+// A savegame that stays loaded in wasm memory, so it doesn't have to be parsed for every query
+#[wasm_bindgen(js_name = SaveGame)]
+pub struct LoadedSaveGame {
+    save_game: SaveGame,
+}
+
+#[wasm_bindgen(js_class = SaveGame)]
+impl LoadedSaveGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(buffer: &[u8]) -> Result<LoadedSaveGame, JsError> {
+        console_error_panic_hook::set_once();
+
+        let file = CompressedSaveFile::new(Cursor::new(buffer))?;
+        Ok(LoadedSaveGame {
+            save_game: SaveGame::load(file)?,
+        })
+    }
+
+    #[wasm_bindgen(js_name = mapWidth)]
+    pub fn map_width(&self) -> Result<u32, JsError> {
+        Ok(self.save_game.map_size()?.0)
+    }
+
+    #[wasm_bindgen(js_name = mapHeight)]
+    pub fn map_height(&self) -> Result<u32, JsError> {
+        Ok(self.save_game.map_size()?.1)
+    }
+
+    #[wasm_bindgen(js_name = linkGraphs, unchecked_return_type = "LinkGraph[]")]
+    pub fn link_graphs(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.save_game.link_graphs()?,
+        )?)
+    }
+
+    #[wasm_bindgen(js_name = cargoTable, unchecked_return_type = "CargoTable")]
+    pub fn cargo_table(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.cargo_table())?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Station[]")]
+    pub fn stations(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.stations()?)?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Town[]")]
+    pub fn towns(&self) -> Result<JsValue, JsError> {
+        Ok(to_js_objects(&self.save_game.towns()?)?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Company[]")]
+    pub fn companies(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.companies()?)?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Industry[]")]
+    pub fn industries(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.industries()?)?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Vehicle[]")]
+    pub fn vehicles(&self) -> Result<JsValue, JsError> {
+        Ok(to_js_objects(&self.save_game.vehicles()?)?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Consist[]")]
+    pub fn consists(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.consists()?)?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Engine[]")]
+    pub fn engines(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.engines()?)?)
+    }
+
+    #[wasm_bindgen(js_name = newGrfConfigs, unchecked_return_type = "NewGrfConfig[]")]
+    pub fn newgrf_configs(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.save_game.newgrf_configs()?,
+        )?)
+    }
+
+    #[wasm_bindgen(js_name = orderLists, unchecked_return_type = "OrderList[]")]
+    pub fn order_lists(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.save_game.order_lists()?,
+        )?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Line[]")]
+    pub fn lines(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.save_game.lines()?)?)
+    }
+
+    pub fn minimap(&self, mode: MinimapMode) -> Result<Minimap, JsError> {
+        Ok(minimap::render_minimap(&self.save_game, mode)?)
+    }
+}
+
+// Structs with flattened fields are written as maps by serde, which would otherwise become JS Maps
+// instead of plain objects
+fn to_js_objects<T: Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&Serializer::new().serialize_maps_as_objects(true))
 }
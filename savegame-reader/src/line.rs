@@ -0,0 +1,189 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::consist::{Consist, ConsistCargo};
+use crate::order::{Destination, OrderList};
+use crate::station::Station;
+use crate::vehicle::VehicleType;
+
+#[wasm_bindgen(typescript_custom_section)]
+const LINE_TS: &'static str = r#"
+export interface LineStop {
+  station: number;
+  name: string;
+  x: number;
+  y: number;
+  waypoint: boolean;
+}
+
+export interface Line {
+  index: number;
+  vehicle_type?: VehicleType;
+  owner?: number;
+  stops: LineStop[];
+  vehicles: number[];
+  cargo: ConsistCargo[];
+  profit_this_year: number;
+  profit_last_year: number;
+}
+"#;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LineStop {
+    pub station: usize,
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    pub waypoint: bool,
+}
+
+// The vehicles sharing an order list, like a line of a transit network. Stops are the stations and
+// waypoints of the orders in order, depots are left out. Profits are summed over all vehicles,
+// `cargo` holding the capacity and load by cargo type, as those of different cargos are counted
+// in different units.
+#[derive(Debug, Clone, Serialize)]
+pub struct Line {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vehicle_type: Option<VehicleType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<u8>,
+    pub stops: Vec<LineStop>,
+    pub vehicles: Vec<usize>,
+    pub cargo: Vec<ConsistCargo>,
+    pub profit_this_year: i64,
+    pub profit_last_year: i64,
+}
+
+impl Line {
+    pub fn from_order_list(
+        list: &OrderList,
+        consists: &HashMap<usize, &Consist>,
+        stations: &HashMap<usize, &Station>,
+    ) -> Line {
+        let stops = list
+            .route
+            .iter()
+            .filter_map(|destination| match *destination {
+                Destination::Station { station } => stations.get(&station),
+                Destination::Waypoint { waypoint } => stations.get(&waypoint),
+                Destination::Depot { .. } => None,
+            })
+            .map(|station| LineStop {
+                station: station.index,
+                name: station.name.clone(),
+                x: station.x,
+                y: station.y,
+                waypoint: station.facilities.waypoint,
+            })
+            .collect();
+
+        let vehicles: Vec<&Consist> = list
+            .vehicles
+            .iter()
+            .filter_map(|vehicle| consists.get(vehicle).copied())
+            .collect();
+        let mut cargo: BTreeMap<u8, ConsistCargo> = BTreeMap::new();
+        for consist_cargo in vehicles.iter().flat_map(|consist| &consist.cargo) {
+            let entry = cargo
+                .entry(consist_cargo.cargo)
+                .or_insert_with(|| ConsistCargo {
+                    cargo: consist_cargo.cargo,
+                    capacity: 0,
+                    count: 0,
+                });
+            entry.capacity += consist_cargo.capacity;
+            entry.count += consist_cargo.count;
+        }
+
+        Line {
+            index: list.index,
+            vehicle_type: vehicles.first().map(|consist| consist.vehicle_type),
+            owner: vehicles.first().map(|consist| consist.owner),
+            stops,
+            vehicles: vehicles.iter().map(|consist| consist.index).collect(),
+            cargo: cargo.into_values().collect(),
+            profit_this_year: vehicles
+                .iter()
+                .map(|consist| consist.profit_this_year)
+                .sum(),
+            profit_last_year: vehicles
+                .iter()
+                .map(|consist| consist.profit_last_year)
+                .sum(),
+        }
+    }
+}
+
+// Order lists without vehicles are left out, they don't run anywhere
+pub fn build_lines(
+    order_lists: &[OrderList],
+    consists: &[Consist],
+    stations: &[Station],
+) -> Vec<Line> {
+    let consists = consists
+        .iter()
+        .map(|consist| (consist.index, consist))
+        .collect();
+    let stations = stations
+        .iter()
+        .map(|station| (station.index, station))
+        .collect();
+    order_lists
+        .iter()
+        .map(|list| Line::from_order_list(list, &consists, &stations))
+        .filter(|line| !line.vehicles.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+
+    #[test]
+    fn test_lines() {
+        let file = File::open("./test-big.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let lines = save_game.lines().unwrap();
+        let consists = save_game.consists().unwrap();
+        assert!(!lines.is_empty());
+
+        // A vehicle runs on a single line
+        let vehicles = lines.iter().flat_map(|line| &line.vehicles);
+        assert!(vehicles.clone().count() <= consists.len());
+        let mut unique = vehicles.collect::<Vec<_>>();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(
+            unique.len(),
+            lines.iter().map(|line| line.vehicles.len()).sum::<usize>()
+        );
+
+        assert!(lines.iter().any(|line| line.stops.len() >= 2));
+        // Each cargo of a line has its own entry, summed over the line's vehicles
+        assert!(lines.iter().all(|line| line
+            .cargo
+            .windows(2)
+            .all(|pair| pair[0].cargo < pair[1].cargo)));
+        assert!(lines
+            .iter()
+            .all(|line| line.cargo.iter().all(|cargo| cargo.capacity
+                == consists
+                    .iter()
+                    .filter(|consist| line.vehicles.contains(&consist.index))
+                    .flat_map(|consist| &consist.cargo)
+                    .filter(|consist_cargo| consist_cargo.cargo == cargo.cargo)
+                    .map(|consist_cargo| consist_cargo.capacity)
+                    .sum::<u32>())));
+        let (width, height) = save_game.map_size().unwrap();
+        assert!(lines
+            .iter()
+            .flat_map(|line| &line.stops)
+            .all(|stop| stop.x < width && stop.y < height));
+    }
+}
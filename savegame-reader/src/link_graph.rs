@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::OWNER_NONE;
+use crate::error::Result;
+use crate::save_file::{SaveVersion, SLV_LINKGRAPH_EDGES};
+use crate::station::{Facilities, Station};
+use crate::table_reader::TableItem;
+
+const INVALID_NODE: u16 = u16::MAX;
+
+#[wasm_bindgen(typescript_custom_section)]
+const LINK_GRAPH_TS: &'static str = r#"
+export interface LinkGraphEdge {
+  dest_node: number;
+  capacity: number;
+  usage: number;
+  travel_time_sum: number;
+  last_unrestricted_update: number;
+  last_restricted_update: number;
+}
+
+export interface LinkGraphNode {
+  xy: number;
+  x: number;
+  y: number;
+  name: string;
+  owner: number;
+  facilities: Facilities;
+  supply: number;
+  demand: number;
+  station: number;
+  last_update: number;
+  edges: LinkGraphEdge[];
+}
+
+export interface LinkGraph {
+  index: number;
+  cargo: number;
+  last_compression: number;
+  nodes: LinkGraphNode[];
+}
+"#;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkGraph {
+    pub index: usize,
+    pub cargo: u8,
+    pub last_compression: i64,
+    pub nodes: Vec<LinkGraphNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkGraphNode {
+    pub xy: u32,
+    pub x: u32,
+    pub y: u32,
+    pub name: String,
+    pub owner: u8,
+    pub facilities: Facilities,
+    pub supply: u32,
+    pub demand: u32,
+    pub station: u16,
+    pub last_update: i64,
+    pub edges: Vec<LinkGraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkGraphEdge {
+    pub dest_node: u16,
+    pub capacity: u32,
+    pub usage: u32,
+    pub travel_time_sum: u64,
+    pub last_unrestricted_update: i64,
+    pub last_restricted_update: i64,
+}
+
+// The parts of a savegame needed to place link graph nodes on the map and name their stations
+pub struct NodeResolver {
+    pub map_width: u32,
+    pub stations: HashMap<usize, Station>,
+}
+
+impl LinkGraph {
+    pub fn from_item(
+        item: &TableItem,
+        version: SaveVersion,
+        resolver: &NodeResolver,
+    ) -> Result<LinkGraph> {
+        Ok(LinkGraph {
+            index: item.index,
+            cargo: item.field("cargo")?,
+            last_compression: item.field("last_compression")?,
+            nodes: item
+                .structs("nodes")
+                .enumerate()
+                .map(|(index, node)| {
+                    LinkGraphNode::from_item(node, index as u16, version, resolver)
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub fn node(&self, index: u16) -> Option<&LinkGraphNode> {
+        self.nodes.get(usize::from(index))
+    }
+}
+
+impl LinkGraphNode {
+    fn from_item(
+        item: &TableItem,
+        index: u16,
+        version: SaveVersion,
+        resolver: &NodeResolver,
+    ) -> Result<LinkGraphNode> {
+        let saved_edges = item.structs("edges").collect::<Vec<_>>();
+        let edges = if version.is_before(SLV_LINKGRAPH_EDGES) {
+            // Edges used to be a linked list through a sparse matrix. It starts at the node's edge
+            // to itself, which only points to the first real edge, and each edge holds the
+            // destination of the edge following it.
+            let mut edges = vec![];
+            let mut dest_node = index;
+            for edge in saved_edges {
+                if dest_node != index {
+                    edges.push(LinkGraphEdge::from_item(edge, dest_node)?);
+                }
+                dest_node = edge.field("next_edge")?;
+                if dest_node == INVALID_NODE {
+                    break;
+                }
+            }
+            edges
+        } else {
+            saved_edges
+                .into_iter()
+                .map(|edge| LinkGraphEdge::from_item(edge, edge.field("dest_node")?))
+                .collect::<Result<_>>()?
+        };
+
+        let station_id: u16 = item.field("station")?;
+        let station = resolver.stations.get(&usize::from(station_id));
+        // Savegames from before SLV_191 don't store the location of a node, OpenTTD takes it
+        // from the station after loading
+        let xy: u32 = match (item.field("xy"), station) {
+            (Ok(xy), _) => xy,
+            (Err(_), Some(station)) => station.xy,
+            (Err(err), None) => return Err(err),
+        };
+
+        Ok(LinkGraphNode {
+            xy,
+            x: xy % resolver.map_width,
+            y: xy / resolver.map_width,
+            name: match station {
+                Some(station) => station.name.clone(),
+                None => format!("Station #{}", station_id),
+            },
+            owner: station.map_or(OWNER_NONE, |station| station.owner),
+            facilities: station
+                .map(|station| station.facilities)
+                .unwrap_or_default(),
+            supply: item.field("supply")?,
+            demand: item.field("demand")?,
+            station: station_id,
+            last_update: item.field("last_update")?,
+            edges,
+        })
+    }
+}
+
+impl LinkGraphEdge {
+    fn from_item(item: &TableItem, dest_node: u16) -> Result<LinkGraphEdge> {
+        Ok(LinkGraphEdge {
+            dest_node,
+            capacity: item.field("capacity")?,
+            usage: item.field("usage")?,
+            // Fields that were added later are zero in older savegames, like OpenTTD defaults them
+            travel_time_sum: item.integer("travel_time_sum").unwrap_or(0) as u64,
+            last_unrestricted_update: item.field("last_unrestricted_update")?,
+            last_restricted_update: item.integer("last_restricted_update").unwrap_or(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::{CompressedSaveFile, DebugSaveFile, SaveVersion};
+    use crate::save_game::SaveGame;
+
+    fn assert_edges_valid(save_game: &SaveGame) {
+        let link_graphs = save_game.link_graphs().unwrap();
+        assert!(!link_graphs.is_empty());
+        assert!(link_graphs
+            .iter()
+            .any(|graph| graph.nodes.iter().any(|node| !node.edges.is_empty())));
+        let (width, height) = save_game.map_size().unwrap();
+        for graph in &link_graphs {
+            for (index, node) in graph.nodes.iter().enumerate() {
+                assert!(node.x < width && node.y < height);
+                assert!(!node.name.is_empty());
+                for edge in &node.edges {
+                    assert_ne!(usize::from(edge.dest_node), index);
+                    assert!(graph.node(edge.dest_node).is_some());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_link_graphs_with_edge_lists() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        assert_edges_valid(&save_game);
+    }
+
+    #[test]
+    fn test_link_graphs_from_tables() {
+        let file = File::open("./test-big.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        assert_edges_valid(&save_game);
+    }
+
+    #[test]
+    fn test_link_graphs_without_edges() {
+        let file = File::open("./test_empty_map.sav.decoded").unwrap();
+        let save_file = DebugSaveFile::new_from_decoded(file, SaveVersion::new(302, 0));
+        let save_game = SaveGame::load(save_file).unwrap();
+        assert!(save_game.link_graphs().unwrap().is_empty());
+    }
+}
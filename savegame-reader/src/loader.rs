@@ -1,67 +1,112 @@
 use std::collections::HashMap;
-use std::io;
-use std::io::prelude::*;
+use std::fmt;
 
 use byteorder::{BigEndian, ReadBytesExt};
 
+use crate::error::{Result, SaveGameError};
 use crate::save_file::SaveFile;
+use crate::schema;
 use crate::table_reader::{read_sparse_table, read_table, read_table_header, Field, TableItem};
 
-#[allow(dead_code)]
-pub fn load_file(mut save_file: impl SaveFile) -> io::Result<HashMap<String, Vec<TableItem>>> {
-    let mut chunks: HashMap<String, Vec<TableItem>> = HashMap::new();
+#[derive(Debug)]
+pub enum Chunk {
+    Table(Vec<TableItem>),
+    Riff(Vec<u8>),
+    // An array chunk without a known schema, of which only the size is known
+    Raw { items: usize, bytes: usize },
+}
+
+impl Chunk {
+    pub fn items(&self) -> Option<&[TableItem]> {
+        match self {
+            Chunk::Table(items) => Some(items),
+            Chunk::Riff(_) | Chunk::Raw { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Chunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Chunk::Table(items) => write!(f, "{} items", items.len()),
+            Chunk::Riff(data) => write!(f, "riff, {} bytes", data.len()),
+            Chunk::Raw { items, bytes } => write!(f, "raw, {} items, {} bytes", items, bytes),
+        }
+    }
+}
+
+pub fn load_file(mut save_file: impl SaveFile) -> Result<HashMap<String, Chunk>> {
+    let mut chunks: HashMap<String, Chunk> = HashMap::new();
 
     loop {
+        let chunk_offset = save_file.position();
         let mut chunk_id = [0; 4];
-        save_file.read_exact(&mut chunk_id)?;
+        save_file
+            .read_exact(&mut chunk_id)
+            .map_err(|err| SaveGameError::from(err).at_position(chunk_offset))?;
 
         if u32::from_be_bytes(chunk_id) == 0 {
             break;
         }
 
-        let chunk_id = chunk_id_from_bytes(&chunk_id);
-        println!("Loading chunk {} ({})", chunk_id, chunk_name_of(chunk_id));
-        let chunk_type = ChunkType::read_from(&mut save_file)?;
-        println!("Chunk type: {:?}", chunk_type);
-        println!("{}", save_file.debug_info());
+        let chunk_id = chunk_id_from_bytes(&chunk_id, chunk_offset)?;
+        let chunk = load_chunk(&mut save_file, chunk_id)
+            .map_err(|err| err.in_chunk(chunk_id, chunk_offset, save_file.position()))?;
+        if let Some(chunk) = chunk {
+            chunks.insert(String::from(chunk_id), chunk);
+        }
+    }
+    Ok(chunks)
+}
+
+fn load_chunk(save_file: &mut impl SaveFile, chunk_id: &str) -> Result<Option<Chunk>> {
+    let type_offset = save_file.position();
+    let type_byte = save_file.read_u8()?;
+    let chunk_type =
+        ChunkType::from_byte(type_byte).ok_or(SaveGameError::UnsupportedChunkType {
+            chunk_type: type_byte & ChunkType::TYPE_MASK,
+            offset: type_offset,
+        })?;
 
-        if chunk_type.has_table_header() {
+    match chunk_type {
+        ChunkType::Table | ChunkType::SparseTable => {
             // SlIterateArray
             // read array length
+            let table_header_offset = save_file.position();
             let table_header_length = save_file.read_gamma()?;
-            assert!(table_header_length > 0, "table header size was 0");
-            // println!("Table header length: {} bytes", table_header_length);
-            let mut fields = read_table_header(&mut save_file)?;
+            if table_header_length == 0 {
+                return Err(SaveGameError::Corrupt {
+                    offset: table_header_offset,
+                    reason: String::from("table header size was 0"),
+                });
+            }
+            let mut fields = read_table_header(save_file)?;
             if chunk_id == "GSDT" {
                 fields.push(Field::new_custom_data())
             }
 
-            match chunk_type {
-                ChunkType::Table => {
-                    let items = read_table(&mut save_file, fields)?;
-                    chunks.insert(String::from(chunk_id), items);
-                }
-                ChunkType::SparseTable => {
-                    let items = read_sparse_table(&mut save_file, fields)?;
-                    chunks.insert(String::from(chunk_id), items);
-                }
-                _ => panic!("unexpected chunk type {:?}", chunk_type),
-            }
+            let items = if chunk_type == ChunkType::Table {
+                read_table(save_file, fields)?
+            } else {
+                read_sparse_table(save_file, fields)?
+            };
+            Ok(Some(Chunk::Table(items)))
         }
-        if chunk_type == ChunkType::Riff {
-            let mut length = usize::from(save_file.read_u8()?) << 16;
+        ChunkType::Riff => {
+            // The upper nibble of the type byte holds the highest bits of the length
+            let mut length = usize::from(type_byte >> 4) << 24;
+            length += usize::from(save_file.read_u8()?) << 16;
             length += usize::from(save_file.read_u16::<BigEndian>()?);
-            println!("Riff length: {}", length);
-            skip_bytes(&mut save_file, length)?;
+            let mut data = vec![0; length];
+            save_file.read_exact(&mut data)?;
+            Ok(Some(Chunk::Riff(data)))
+        }
+        ChunkType::Array | ChunkType::SparseArray => {
+            let decoder = schema::decoder_for(chunk_id, save_file.version());
+            let sparse = chunk_type == ChunkType::SparseArray;
+            Ok(Some(schema::read_array(save_file, sparse, decoder)?))
         }
-        println!();
     }
-    Ok(chunks)
-}
-
-fn skip_bytes(save_file: &mut impl Read, bytes: usize) -> io::Result<()> {
-    let mut buf = vec![0; bytes];
-    save_file.read_exact(&mut buf)
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -74,120 +119,43 @@ enum ChunkType {
 }
 
 impl ChunkType {
-    fn read_from(reader: &mut impl Read) -> io::Result<ChunkType> {
-        const TYPE_MASK: u8 = 0xF;
-        Ok(match reader.read_u8()? & TYPE_MASK {
+    const TYPE_MASK: u8 = 0xF;
+
+    fn from_byte(byte: u8) -> Option<ChunkType> {
+        Some(match byte & ChunkType::TYPE_MASK {
             0 => ChunkType::Riff,
             1 => ChunkType::Array,
             2 => ChunkType::SparseArray,
             3 => ChunkType::Table,
             4 => ChunkType::SparseTable,
-            _ => panic!("Unknown chunk type"),
+            _ => return None,
         })
     }
-
-    fn has_table_header(&self) -> bool {
-        match self {
-            ChunkType::Table | ChunkType::SparseTable => true,
-            _ => false,
-        }
-    }
 }
 
-fn chunk_id_from_bytes(bytes: &[u8; 4]) -> &str {
-    std::str::from_utf8(bytes).unwrap()
-}
-
-fn chunk_name_of(chunk_id: &str) -> String {
-    match chunk_id {
-        "AIPL" => "AI companies".to_string(),
-        "ANIT" => "Animated tiles".to_string(),
-        "ERNW" => "Engine renewal".to_string(),
-        "CMDL" => "Cargo deliveries".to_string(),
-        "CMPU" => "Cargo pickups".to_string(),
-        "CAPA" => "Cargo packets".to_string(),
-        "CHTS" => "Cheats used".to_string(),
-        "PLYR" => "Player companies".to_string(),
-        "DEPT" => "Depots".to_string(),
-        "PRIC" => "Prices".to_string(),
-        "CAPR" => "Cargo payment rates (only in pre 126 savegames)".to_string(),
-        "ECMY" => "Economy variables".to_string(),
-        "CAPY" => "Cargo payments".to_string(),
-        "ENGN" => "Engines".to_string(),
-        "ENGS" => "Engine ID mappings (legacy)".to_string(),
-        "EIDS" => "Engine ID mappings".to_string(),
-        "GSDT" => "Game script configs".to_string(),
-        "GSTR" => "Game strings".to_string(),
-        "GLOG" => "Game log".to_string(),
-        "GOAL" => "Goals".to_string(),
-        "GRPS" => "Groups (of vehicles?)".to_string(),
-        "INDY" => "Industries".to_string(),
-        "IBLD" => "Industry builder".to_string(),
-        "ITBL" => "Industry type build data".to_string(),
-        "RAIL" => "Rail type labels".to_string(),
-        "LEAE" => "League table elements".to_string(),
-        "LEAT" => "League tables".to_string(),
-        "LGRP" => "Link graphs".to_string(),
-        "LGRJ" => "Link graph jobs".to_string(),
-        "LGRS" => "Link graph schedule".to_string(),
-        "MAPS" => "Map size".to_string(),
-        "MAPT" => "Map tile types".to_string(),
-        "MAPH" => "Map tile heights".to_string(),
-        "MAPO" => "Map tile ownership information".to_string(),
-        "MAP2" => "Map tile indices of towns, industries and stations".to_string(),
-        "M3LO" => "Map tile general purpose".to_string(),
-        "M3HI" => "Map tile general purpose".to_string(),
-        "MAP5" => "Map tile general purpose".to_string(),
-        "MAPE" => "Map tile general purpose".to_string(),
-        "MAP7" => "Map tile NewGRF support".to_string(),
-        "MAP8" => "Map tile general purpose".to_string(),
-        "DATE" => "Ingame date".to_string(),
-        "VIEW" => "Viewport info".to_string(),
-        "NGRF" => "Loaded NewGRFs".to_string(),
-        "OBJS" => "Map objects".to_string(),
-        "ORDR" => "Orders".to_string(),
-        "ORDL" => "Order lists".to_string(),
-        "BKOR" => "Order backups".to_string(),
-        "OPTS" => "Settings (legacy)".to_string(),
-        "PATS" => "Settings".to_string(),
-        "SIGN" => "Signs".to_string(),
-        "STNS" => "Stations (legacy)".to_string(),
-        "STNN" => "Stations".to_string(),
-        "ROAD" => "Road stops".to_string(),
-        "PSAC" => "Persistent storage".to_string(),
-        "STPE" => "Story page elements".to_string(),
-        "STPA" => "Story pages".to_string(),
-        "NAME" => "Old names (legacy)".to_string(),
-        "SUBS" => "Subsidies".to_string(),
-        "CITY" => "Towns".to_string(),
-        "VEHS" => "Vehicles".to_string(),
-        "CHKP" => "Waypoints (legacy)".to_string(),
-        "ATID" => "_airporttile_mngr".to_string(),
-        "APID" => "_airport_mngr".to_string(),
-        "IIDS" => "_industry_mngr".to_string(),
-        "TIDS" => "_industile_mngr".to_string(),
-        "OBID" => "_object_mngr".to_string(),
-        "HIDS" => "_house_mngr".to_string(),
-        unknown => format!("unknown chunk '{}'", unknown),
-    }
+fn chunk_id_from_bytes(bytes: &[u8; 4], offset: u64) -> Result<&str> {
+    std::str::from_utf8(bytes).map_err(|_| SaveGameError::InvalidUtf8 { offset })
 }
 
 #[cfg(test)]
 mod tests {
     use std::fs::File;
+    use std::io::{Cursor, Read};
 
-    use crate::loader::load_file;
+    use crate::error::SaveGameError;
+    use crate::loader::{load_file, Chunk};
     use crate::save_file::CompressedSaveFile;
-    use crate::save_file::DebugSaveFile;
+    use crate::save_file::{DebugSaveFile, SaveVersion};
 
     #[test]
     fn test_load_valid_debug_file() {
-        let file = File::open("./test-big.sav.decoded").unwrap();
-        let save_file = DebugSaveFile::new_from_decoded(file);
+        let file = File::open("./test_empty_map.sav.decoded").unwrap();
+        let save_file = DebugSaveFile::new_from_decoded(file, SaveVersion::new(302, 0));
         // let file = File::open("test_empty_map.sav").unwrap();
         // let save_file = CompressedSaveFile::new(file);
 
         let chunks = load_file(save_file).unwrap();
+        assert!(chunks.contains_key("LGRP"));
 
         // let mut out_file = File::create(Path::new("../out.json")).unwrap();
         // out_file
@@ -210,11 +178,86 @@ mod tests {
     #[test]
     fn test_load_valid_compressed_file() {
         let file = File::open("./test-big.sav").unwrap();
-        let save_file = CompressedSaveFile::new(file);
+        let save_file = CompressedSaveFile::new(file).unwrap();
         // let file = File::open("test_empty_map.sav").unwrap();
-        // let save_file = CompressedSaveFile::new(file);
+        // let save_file = CompressedSaveFile::new(file).unwrap();
         println!("Savefile Version: {}", save_file.version);
 
         let chunks = load_file(save_file).unwrap();
+        assert!(!chunks.get("LGRP").unwrap().items().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_legacy_array_chunks() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_file = CompressedSaveFile::new(file).unwrap();
+
+        let chunks = load_file(save_file).unwrap();
+        assert_eq!(chunks.get("CITY").unwrap().items().unwrap().len(), 432);
+        assert_eq!(chunks.get("STNN").unwrap().items().unwrap().len(), 422);
+        assert_eq!(chunks.get("VEHS").unwrap().items().unwrap().len(), 924);
+        assert_eq!(chunks.get("LGRP").unwrap().items().unwrap().len(), 84);
+        assert_eq!(chunks.get("CAPA").unwrap().items().unwrap().len(), 2956);
+        assert_eq!(chunks.get("INDY").unwrap().items().unwrap().len(), 744);
+        assert_eq!(chunks.get("IIDS").unwrap().items().unwrap().len(), 240);
+        assert_eq!(chunks.get("PLYR").unwrap().items().unwrap().len(), 3);
+        assert_eq!(chunks.get("ORDR").unwrap().items().unwrap().len(), 763);
+        assert_eq!(chunks.get("ORDL").unwrap().items().unwrap().len(), 175);
+        assert_eq!(chunks.get("ENGN").unwrap().items().unwrap().len(), 256);
+        assert_eq!(chunks.get("EIDS").unwrap().items().unwrap().len(), 256);
+        assert_eq!(
+            chunks.get("DEPT").unwrap().to_string(),
+            "raw, 126 items, 1890 bytes"
+        );
+    }
+
+    #[test]
+    fn test_skip_unknown_array_chunks() {
+        let data = [
+            b"XARR".as_slice(),
+            // Array: two items of 3 and 1 bytes with an empty item in between
+            &[1, 4, 1, 2, 3, 1, 2, 9, 0],
+            b"XSPA",
+            // SparseArray: one item at index 5 with 2 bytes of data
+            &[2, 4, 5, 7, 8, 0],
+            &[0, 0, 0, 0],
+        ]
+        .concat();
+        let save_file =
+            DebugSaveFile::new_from_decoded(Cursor::new(data), SaveVersion::new(302, 0));
+
+        let chunks = load_file(save_file).unwrap();
+        assert!(matches!(
+            chunks.get("XARR"),
+            Some(Chunk::Raw { items: 2, bytes: 4 })
+        ));
+        assert!(matches!(
+            chunks.get("XSPA"),
+            Some(Chunk::Raw { items: 1, bytes: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_load_truncated_file() {
+        let mut buf = Vec::new();
+        File::open("./test_empty_map.sav.decoded")
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        buf.truncate(buf.len() / 2);
+        let save_file = DebugSaveFile::new_from_decoded(Cursor::new(buf), SaveVersion::new(302, 0));
+
+        match load_file(save_file) {
+            Err(SaveGameError::InChunk { source, .. }) => {
+                assert!(matches!(*source, SaveGameError::Truncated { .. }))
+            }
+            result => panic!("expected truncation error, got {:?}", result.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_load_unknown_format() {
+        let result = CompressedSaveFile::new(Cursor::new(b"ABCD\0\0\0\0".to_vec()));
+        assert!(matches!(result, Err(SaveGameError::UnknownFormat(_))));
     }
 }
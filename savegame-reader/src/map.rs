@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::common::{OWNER_NONE, OWNER_TOWN};
+use crate::error::{Result, SaveGameError};
+use crate::loader::Chunk;
+use crate::save_file::{SaveVersion, SLV_194};
+use crate::tile::Tile;
+
+const SLV_42: u16 = 42;
+
+// The RIFF chunks holding the map layers, which the tile map takes ownership of
+const MAP_LAYERS: [&str; 10] = [
+    "MAPT", "MAPH", "MAPO", "MAP2", "M3LO", "M3HI", "MAP5", "MAPE", "MAP7", "MAP8",
+];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum TileType {
+    Clear,
+    Railway,
+    Road,
+    House,
+    Trees,
+    Station,
+    Water,
+    Void,
+    Industry,
+    TunnelBridge,
+    Object,
+}
+
+impl TileType {
+    fn from_byte(byte: u8) -> Option<TileType> {
+        Some(match byte {
+            0 => TileType::Clear,
+            1 => TileType::Railway,
+            2 => TileType::Road,
+            3 => TileType::House,
+            4 => TileType::Trees,
+            5 => TileType::Station,
+            6 => TileType::Water,
+            7 => TileType::Void,
+            8 => TileType::Industry,
+            9 => TileType::TunnelBridge,
+            10 => TileType::Object,
+            _ => return None,
+        })
+    }
+}
+
+// The fields of OpenTTD's `Tile` and `TileExtended`. `type_bits` still holds the tropic zone and
+// bridge bits next to the tile type in its upper nibble.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MapTile {
+    pub tile_type: TileType,
+    pub height: u8,
+    pub owner: u8,
+    pub type_bits: u8,
+    pub m1: u8,
+    pub m2: u16,
+    pub m3: u8,
+    pub m4: u8,
+    pub m5: u8,
+    pub m6: u8,
+    pub m7: u8,
+    pub m8: u16,
+}
+
+#[derive(Debug)]
+pub struct TileMap {
+    width: u32,
+    height: u32,
+    type_bits: Vec<u8>,
+    heights: Vec<u8>,
+    m1: Vec<u8>,
+    m2: Vec<u16>,
+    m3: Vec<u8>,
+    m4: Vec<u8>,
+    m5: Vec<u8>,
+    m6: Vec<u8>,
+    m7: Vec<u8>,
+    m8: Vec<u16>,
+}
+
+impl TileMap {
+    // Takes the map layers out of `chunks`. A map that can't be decoded leaves them in place.
+    pub fn from_chunks(
+        chunks: &mut HashMap<String, Chunk>,
+        version: SaveVersion,
+    ) -> Result<TileMap> {
+        let (width, height) = map_size(chunks)?;
+        TileMap::from_layers(width, height, chunks, version)
+    }
+
+    pub(crate) fn from_layers(
+        width: u32,
+        height: u32,
+        layers: &mut HashMap<String, Chunk>,
+        version: SaveVersion,
+    ) -> Result<TileMap> {
+        let size = width as usize * height as usize;
+        // Old savegames packed four tiles worth of two bit m6 values into each byte
+        let m6_size = if version.is_before(SLV_42) {
+            size / 4
+        } else {
+            size
+        };
+        let layer_sizes = [
+            ("MAPT", size),
+            ("MAPH", if version.is_before(SLV_194) { 0 } else { size }),
+            ("MAPO", size),
+            ("MAP2", size * 2),
+            ("M3LO", size),
+            ("M3HI", size),
+            ("MAP5", size),
+            ("MAPE", m6_size),
+            ("MAP7", size),
+            ("MAP8", size * 2),
+        ];
+        // Every layer is checked before any is taken, so a failed decode doesn't lose them
+        for (chunk_id, expected) in layer_sizes {
+            match riff_data(layers, chunk_id) {
+                Some(data) if expected != 0 && data.len() != expected => {
+                    return Err(layer_size_mismatch(chunk_id, data.len(), expected))
+                }
+                _ => {}
+            }
+        }
+
+        let mut type_bits = byte_layer(layers, "MAPT", size);
+        let heights = if version.is_before(SLV_194) {
+            // The height used to be stored in the lower nibble of the tile type
+            type_bits
+                .iter_mut()
+                .map(|type_height| {
+                    let height = *type_height & 0x0F;
+                    *type_height &= 0xF0;
+                    height
+                })
+                .collect()
+        } else {
+            byte_layer(layers, "MAPH", size)
+        };
+
+        let m6 = if version.is_before(SLV_42) {
+            byte_layer(layers, "MAPE", m6_size)
+                .iter()
+                .flat_map(|byte| {
+                    [
+                        byte & 0x03,
+                        (byte >> 2) & 0x03,
+                        (byte >> 4) & 0x03,
+                        byte >> 6,
+                    ]
+                })
+                .collect()
+        } else {
+            byte_layer(layers, "MAPE", size)
+        };
+
+        Ok(TileMap {
+            width,
+            height,
+            type_bits,
+            heights,
+            m1: byte_layer(layers, "MAPO", size),
+            m2: word_layer(layers, "MAP2", size),
+            m3: byte_layer(layers, "M3LO", size),
+            m4: byte_layer(layers, "M3HI", size),
+            m5: byte_layer(layers, "MAP5", size),
+            m6,
+            m7: byte_layer(layers, "MAP7", size),
+            m8: word_layer(layers, "MAP8", size),
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn tile(&self, x: u32, y: u32) -> Option<MapTile> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.tile_at(y as usize * self.width as usize + x as usize))
+    }
+
+    pub fn decode_tile(&self, x: u32, y: u32) -> Option<Tile> {
+        self.tile(x, y).map(|tile| Tile::from(&tile))
+    }
+
+    pub fn tile_at(&self, index: usize) -> MapTile {
+        let type_bits = self.type_bits[index];
+        // Unknown tile types can't be placed on the map by OpenTTD, treat them like the map edge
+        let tile_type = TileType::from_byte(type_bits >> 4).unwrap_or(TileType::Void);
+        let m1 = self.m1[index];
+        let owner = match tile_type {
+            TileType::House => OWNER_TOWN,
+            TileType::Industry | TileType::Void => OWNER_NONE,
+            _ => m1 & 0x1F,
+        };
+
+        MapTile {
+            tile_type,
+            height: self.heights[index],
+            owner,
+            type_bits,
+            m1,
+            m2: self.m2[index],
+            m3: self.m3[index],
+            m4: self.m4[index],
+            m5: self.m5[index],
+            m6: self.m6[index],
+            m7: self.m7[index],
+            m8: self.m8[index],
+        }
+    }
+}
+
+pub(crate) fn map_size(chunks: &HashMap<String, Chunk>) -> Result<(u32, u32)> {
+    let corrupt = |reason: &str| SaveGameError::Corrupt {
+        offset: 0,
+        reason: format!("invalid MAPS chunk: {}", reason),
+    };
+
+    let (width, height) = match chunks.get("MAPS") {
+        Some(Chunk::Riff(data)) if data.len() >= 8 => (
+            u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+            u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+        ),
+        Some(Chunk::Table(items)) => {
+            let item = items.first().ok_or_else(|| corrupt("no items"))?;
+            let dimension = |key| {
+                item.integer(key)
+                    .and_then(|value| u32::try_from(value).ok())
+                    .ok_or_else(|| corrupt("missing dimension"))
+            };
+            (dimension("dim_x")?, dimension("dim_y")?)
+        }
+        Some(_) => return Err(corrupt("unexpected layout")),
+        None => return Err(SaveGameError::MissingChunk(String::from("MAPS"))),
+    };
+    if width == 0 || height == 0 {
+        return Err(corrupt(&format!("map size {}x{}", width, height)));
+    }
+    Ok((width, height))
+}
+
+fn riff_data<'a>(chunks: &'a HashMap<String, Chunk>, chunk_id: &str) -> Option<&'a [u8]> {
+    match chunks.get(chunk_id) {
+        Some(Chunk::Riff(data)) => Some(data),
+        _ => None,
+    }
+}
+
+// Moves the chunks of the map layers out of `chunks`, leaving the other chunks
+pub(crate) fn take_layers(chunks: &mut HashMap<String, Chunk>) -> HashMap<String, Chunk> {
+    MAP_LAYERS
+        .iter()
+        .filter_map(|chunk_id| chunks.remove_entry(*chunk_id))
+        .collect()
+}
+
+// Layers that were introduced after the savegame was written are zero filled, like OpenTTD does.
+// The sizes have been checked by `TileMap::from_layers` already.
+fn take_riff(layers: &mut HashMap<String, Chunk>, chunk_id: &str) -> Option<Vec<u8>> {
+    match layers.remove(chunk_id) {
+        Some(Chunk::Riff(data)) => Some(data),
+        _ => None,
+    }
+}
+
+fn byte_layer(layers: &mut HashMap<String, Chunk>, chunk_id: &str, size: usize) -> Vec<u8> {
+    take_riff(layers, chunk_id).unwrap_or_else(|| vec![0; size])
+}
+
+fn word_layer(layers: &mut HashMap<String, Chunk>, chunk_id: &str, size: usize) -> Vec<u16> {
+    match take_riff(layers, chunk_id) {
+        Some(data) => data
+            .chunks_exact(2)
+            .map(|word| u16::from_be_bytes([word[0], word[1]]))
+            .collect(),
+        None => vec![0; size],
+    }
+}
+
+fn layer_size_mismatch(chunk_id: &str, actual: usize, expected: usize) -> SaveGameError {
+    SaveGameError::Corrupt {
+        offset: 0,
+        reason: format!(
+            "map layer {} has {} bytes, expected {}",
+            chunk_id, actual, expected
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs::File;
+
+    use crate::loader::{load_file, Chunk};
+    use crate::map::{TileMap, TileType};
+    use crate::save_file::{CompressedSaveFile, DebugSaveFile, SaveVersion};
+    use crate::save_game::SaveGame;
+
+    #[test]
+    fn test_read_tile_map() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_file = CompressedSaveFile::new(file).unwrap();
+        let version = save_file.version;
+        let mut chunks = load_file(save_file).unwrap();
+
+        let map = TileMap::from_chunks(&mut chunks, version).unwrap();
+        assert_eq!(map.width() * map.height(), 1024 * 1024);
+        // The layers are moved into the map instead of being held twice
+        assert!(!chunks.contains_key("MAP5"));
+        assert!(chunks.contains_key("MAPS"));
+        // The northern border of the map is always void
+        assert_eq!(map.tile(0, 0).unwrap().tile_type, TileType::Void);
+        assert!(map.tile(map.width(), 0).is_none());
+        assert!((0..map.width() * map.height())
+            .any(|index| map.tile_at(index as usize).tile_type == TileType::Station));
+    }
+
+    #[test]
+    fn test_tile_map_is_decoded_once() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let map = save_game.tile_map().unwrap();
+        assert!(std::ptr::eq(map, save_game.tile_map().unwrap()));
+    }
+
+    #[test]
+    fn test_read_tile_map_with_table_map_size() {
+        let file = File::open("./test_empty_map.sav.decoded").unwrap();
+        let save_file = DebugSaveFile::new_from_decoded(file, SaveVersion::new(302, 0));
+        let mut chunks = load_file(save_file).unwrap();
+
+        let map = TileMap::from_chunks(&mut chunks, SaveVersion::new(302, 0)).unwrap();
+        assert_eq!(map.width(), 256);
+        assert_eq!(map.height(), 256);
+    }
+
+    #[test]
+    fn test_read_type_and_height_before_split() {
+        let mut chunks = HashMap::new();
+        chunks.insert(
+            String::from("MAPS"),
+            Chunk::Riff(vec![0, 0, 0, 2, 0, 0, 0, 1]),
+        );
+        chunks.insert(String::from("MAPT"), Chunk::Riff(vec![0x73, 0x15]));
+        chunks.insert(String::from("MAPO"), Chunk::Riff(vec![0, 0x21]));
+
+        let map = TileMap::from_chunks(&mut chunks, SaveVersion::new(193, 0)).unwrap();
+        let tile = map.tile(1, 0).unwrap();
+        assert_eq!(tile.tile_type, TileType::Railway);
+        assert_eq!(tile.height, 5);
+        assert_eq!(tile.owner, 1);
+        assert_eq!(map.tile(0, 0).unwrap().tile_type, TileType::Void);
+        assert_eq!(map.tile(0, 0).unwrap().height, 3);
+    }
+
+    #[test]
+    fn test_failed_decode_keeps_layers() {
+        let mut chunks = HashMap::new();
+        chunks.insert(
+            String::from("MAPS"),
+            Chunk::Riff(vec![0, 0, 0, 2, 0, 0, 0, 1]),
+        );
+        chunks.insert(String::from("MAPT"), Chunk::Riff(vec![0x10, 0x10]));
+        chunks.insert(String::from("MAP2"), Chunk::Riff(vec![0; 3]));
+
+        assert!(TileMap::from_chunks(&mut chunks, SaveVersion::new(302, 0)).is_err());
+        assert!(chunks.contains_key("MAPT"));
+        assert!(chunks.contains_key("MAP2"));
+    }
+}
@@ -0,0 +1,546 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use wasm_bindgen::prelude::{wasm_bindgen, JsError};
+
+use crate::common::{has_bit, OWNER_TOWN, OWNER_WATER};
+use crate::error::Result;
+use crate::map::{MapTile, TileMap, TileType};
+use crate::save_game::SaveGame;
+use crate::tile::{ClearGround, StationType, Tile, TransportType, TreeGround, WaterClass};
+
+// The colour modes of OpenTTD's smallmap window
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MinimapMode {
+    Contours,
+    Vehicles,
+    Industries,
+    Routes,
+    Vegetation,
+    Owners,
+}
+
+type Rgb = [u8; 3];
+
+const BLACK: Rgb = [0x00, 0x00, 0x00];
+const WHITE: Rgb = [0xfc, 0xfc, 0xfc];
+const WATER: Rgb = [0x2c, 0x54, 0xa8];
+const RAIL: Rgb = [0x38, 0x38, 0x38];
+const ROAD: Rgb = [0x80, 0x80, 0x80];
+const STATION: Rgb = [0xa8, 0x44, 0x44];
+const HOUSE: Rgb = [0xb4, 0x98, 0x74];
+const INDUSTRY: Rgb = [0xc8, 0x38, 0x38];
+const OBJECT: Rgb = [0x94, 0x74, 0x54];
+const LAND: Rgb = [0x5c, 0x8c, 0x40];
+
+const VS_HIDDEN: u8 = 0;
+
+// The main shade of each company colour
+const COMPANY_COLOURS: [Rgb; 16] = [
+    [0x1c, 0x34, 0x8c], // dark blue
+    [0x4c, 0x94, 0x60], // pale green
+    [0xd4, 0x58, 0xa0], // pink
+    [0xec, 0xc4, 0x20], // yellow
+    [0xc8, 0x20, 0x20], // red
+    [0x54, 0xac, 0xdc], // light blue
+    [0x40, 0xa8, 0x30], // green
+    [0x18, 0x60, 0x24], // dark green
+    [0x28, 0x54, 0xc8], // blue
+    [0xe4, 0xd0, 0x98], // cream
+    [0x9c, 0x6c, 0xa4], // mauve
+    [0x6c, 0x30, 0x94], // purple
+    [0xec, 0x7c, 0x14], // orange
+    [0x80, 0x4c, 0x24], // brown
+    [0x8c, 0x8c, 0x8c], // grey
+    [0xf4, 0xf4, 0xf4], // white
+];
+
+// The smallmap colour of each original industry type, in the order of OpenTTD's IndustryTypes
+const INDUSTRY_COLOURS: [Rgb; 37] = [
+    [0x20, 0x20, 0x20],
+    [0xd0, 0x20, 0x20],
+    [0xb8, 0x78, 0x30],
+    [0x18, 0x6c, 0x18],
+    [0x48, 0xc0, 0xe0],
+    [0xe0, 0xe0, 0xe0],
+    [0xe0, 0x60, 0xb0],
+    [0x9c, 0x6c, 0xa4],
+    [0xa0, 0xa0, 0xb8],
+    [0xe8, 0xc8, 0x40],
+    [0xc0, 0x70, 0x40],
+    [0x40, 0x40, 0x40],
+    [0xf0, 0xe0, 0x90],
+    [0xe8, 0x80, 0x20],
+    [0xf0, 0xf0, 0xf0],
+    [0xf0, 0xc0, 0x00],
+    [0xf0, 0xe0, 0x90],
+    [0xa0, 0xe0, 0xf0],
+    [0x70, 0x58, 0x90],
+    [0x90, 0xc8, 0x30],
+    [0x60, 0x80, 0x20],
+    [0x30, 0x60, 0xd0],
+    [0x60, 0x90, 0xf0],
+    [0xe0, 0x60, 0xb0],
+    [0xe8, 0xc8, 0x40],
+    [0x90, 0x58, 0x20],
+    [0xf0, 0xa0, 0xd0],
+    [0xd0, 0x40, 0x90],
+    [0x50, 0xb0, 0x50],
+    [0x60, 0x30, 0x18],
+    [0xf0, 0x70, 0x70],
+    [0xc8, 0x30, 0x60],
+    [0x40, 0xd0, 0xd0],
+    [0x90, 0xe0, 0x40],
+    [0xb0, 0xd8, 0xf8],
+    [0xa8, 0x70, 0x30],
+    [0xf8, 0xf8, 0xe8],
+];
+
+// Train, road vehicle, ship and aircraft
+const VEHICLE_COLOURS: [Rgb; 4] = [
+    [0xe0, 0x20, 0x20],
+    [0xf0, 0xd0, 0x20],
+    [0x60, 0xb8, 0xf0],
+    WHITE,
+];
+
+#[wasm_bindgen]
+pub struct Minimap {
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl Minimap {
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Copies the pixels out of wasm memory, ready to be wrapped in an `ImageData`
+    pub fn rgba(&self) -> Vec<u8> {
+        self.rgba.clone()
+    }
+
+    #[wasm_bindgen(js_name = toPng)]
+    pub fn to_png(&self) -> std::result::Result<Vec<u8>, JsError> {
+        Ok(self.encode_png()?)
+    }
+}
+
+impl Minimap {
+    pub fn pixels(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    pub fn encode_png(&self) -> io::Result<Vec<u8>> {
+        let mut png = vec![];
+        let mut encoder = png::Encoder::new(&mut png, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.rgba)?;
+        Ok(png)
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, colour: Rgb) {
+        if x < self.width && y < self.height {
+            let offset = (y as usize * self.width as usize + x as usize) * 4;
+            self.rgba[offset..offset + 4].copy_from_slice(&[colour[0], colour[1], colour[2], 0xff]);
+        }
+    }
+}
+
+// Renders one pixel per tile, with the north corner of the map in the top left
+pub fn render_minimap(save_game: &SaveGame, mode: MinimapMode) -> Result<Minimap> {
+    let map = save_game.tile_map()?;
+    let max_height = (0..map.width() as usize * map.height() as usize)
+        .map(|index| map.tile_at(index).height)
+        .max()
+        .unwrap_or(0);
+
+    // Industry types a NewGRF took over don't have an original colour
+    let newgrf_industry_types = save_game
+        .items("IIDS")
+        .iter()
+        .filter(|entity| entity.integer("grfid").is_some_and(|grfid| grfid != 0))
+        .map(|entity| entity.index)
+        .collect::<HashSet<_>>();
+    let mut industry_types = index_values(save_game, "INDY", "type");
+    industry_types
+        .retain(|_, industry_type| !newgrf_industry_types.contains(&usize::from(*industry_type)));
+    let company_colours = index_values(save_game, "PLYR", "colour");
+
+    let mut minimap = Minimap {
+        width: map.width(),
+        height: map.height(),
+        rgba: Vec::with_capacity(map.width() as usize * map.height() as usize * 4),
+    };
+    for index in 0..map.width() as usize * map.height() as usize {
+        let tile = map.tile_at(index);
+        let colour = match mode {
+            MinimapMode::Contours => contour_colour(&tile, max_height),
+            MinimapMode::Vehicles => dim(contour_colour(&tile, max_height)),
+            MinimapMode::Industries => industry_colour(&tile, &industry_types),
+            MinimapMode::Routes => route_colour(&tile),
+            MinimapMode::Vegetation => vegetation_colour(&tile),
+            MinimapMode::Owners => owner_colour(&tile, &company_colours),
+        };
+        minimap
+            .rgba
+            .extend_from_slice(&[colour[0], colour[1], colour[2], 0xff]);
+    }
+
+    if mode == MinimapMode::Vehicles {
+        draw_vehicles(save_game, map, &mut minimap);
+    }
+    Ok(minimap)
+}
+
+// Maps the pool index of each item of a chunk to one of its fields
+fn index_values(save_game: &SaveGame, chunk_id: &str, key: &str) -> HashMap<usize, u8> {
+    save_game
+        .items(chunk_id)
+        .iter()
+        .filter_map(|item| Some((item.index, u8::try_from(item.integer(key)?).ok()?)))
+        .collect()
+}
+
+fn blend(from: Rgb, to: Rgb, weight: f32) -> Rgb {
+    let channel = |index: usize| {
+        (f32::from(from[index]) + (f32::from(to[index]) - f32::from(from[index])) * weight) as u8
+    };
+    [channel(0), channel(1), channel(2)]
+}
+
+// Terrain shown behind the highlighted layer of a mode
+fn dim(colour: Rgb) -> Rgb {
+    blend(colour, [0xd0, 0xd0, 0xd0], 0.6)
+}
+
+fn height_colour(height: u8, max_height: u8) -> Rgb {
+    const LOW: Rgb = [0x34, 0x78, 0x2c];
+    const MID: Rgb = [0xa4, 0xb4, 0x54];
+    const HIGH: Rgb = [0xc8, 0xa0, 0x6c];
+
+    let weight = f32::from(height) / f32::from(max_height.max(1));
+    if weight < 0.5 {
+        blend(LOW, MID, weight * 2.0)
+    } else {
+        blend(MID, HIGH, (weight - 0.5) * 2.0)
+    }
+}
+
+fn is_water(tile: &MapTile) -> bool {
+    match Tile::from(tile) {
+        Tile::Water { .. } => true,
+        Tile::Station { water_class, .. } => water_class != WaterClass::Invalid,
+        _ => false,
+    }
+}
+
+fn contour_colour(tile: &MapTile, max_height: u8) -> Rgb {
+    match Tile::from(tile) {
+        Tile::Void => BLACK,
+        Tile::Water { .. } => WATER,
+        Tile::Railway { .. } => RAIL,
+        Tile::Road { .. } => ROAD,
+        Tile::Station { .. } => STATION,
+        Tile::TunnelBridge { transport, .. } => match transport {
+            TransportType::Rail => RAIL,
+            TransportType::Road => ROAD,
+            TransportType::Water => WATER,
+        },
+        _ => height_colour(tile.height, max_height),
+    }
+}
+
+fn industry_colour(tile: &MapTile, industry_types: &HashMap<usize, u8>) -> Rgb {
+    match Tile::from(tile) {
+        Tile::Industry { industry, .. } => match industry_types.get(&usize::from(industry)) {
+            Some(industry_type) => INDUSTRY_COLOURS
+                .get(usize::from(*industry_type))
+                .copied()
+                .unwrap_or(INDUSTRY),
+            None => INDUSTRY,
+        },
+        Tile::Void => BLACK,
+        _ if is_water(tile) => dim(WATER),
+        _ => dim(LAND),
+    }
+}
+
+fn route_colour(tile: &MapTile) -> Rgb {
+    match Tile::from(tile) {
+        Tile::Void => BLACK,
+        Tile::Railway { .. } => RAIL,
+        Tile::Road { .. } => ROAD,
+        Tile::TunnelBridge { transport, .. } => match transport {
+            TransportType::Rail => RAIL,
+            TransportType::Road => ROAD,
+            TransportType::Water => WATER,
+        },
+        Tile::Station { station_type, .. } => match station_type {
+            StationType::Rail | StationType::RailWaypoint => [0xe8, 0x44, 0x44],
+            StationType::Airport => [0x60, 0xb8, 0xf0],
+            StationType::Truck => [0xec, 0x7c, 0x14],
+            StationType::Bus | StationType::RoadWaypoint => [0xf0, 0xd0, 0x20],
+            StationType::Dock | StationType::Buoy | StationType::Oilrig => [0x30, 0xc0, 0xc0],
+        },
+        _ if is_water(tile) => dim(WATER),
+        _ => dim(LAND),
+    }
+}
+
+fn vegetation_colour(tile: &MapTile) -> Rgb {
+    match Tile::from(tile) {
+        Tile::Void => BLACK,
+        Tile::Water { .. } => WATER,
+        Tile::Clear {
+            ground, density, ..
+        } => match ground {
+            ClearGround::Grass => blend(
+                [0x8c, 0x9c, 0x48],
+                [0x5c, 0x94, 0x38],
+                f32::from(density) / 3.0,
+            ),
+            ClearGround::Rough => [0x6c, 0x74, 0x3c],
+            ClearGround::Rocks => [0x94, 0x94, 0x94],
+            ClearGround::Fields => [0xc8, 0xb0, 0x48],
+            ClearGround::Snow => [0xf0, 0xf0, 0xf0],
+            ClearGround::Desert => [0xe0, 0xc8, 0x88],
+        },
+        Tile::Trees { ground, count, .. } => {
+            let trees = match ground {
+                TreeGround::SnowDesert | TreeGround::RoughSnow => [0x5c, 0x80, 0x64],
+                _ => [0x24, 0x64, 0x1c],
+            };
+            blend([0x5c, 0x94, 0x38], trees, f32::from(count) / 4.0)
+        }
+        Tile::House { .. } => HOUSE,
+        Tile::Industry { .. } => INDUSTRY,
+        Tile::Object { .. } => OBJECT,
+        _ => ROAD,
+    }
+}
+
+fn owner_colour(tile: &MapTile, company_colours: &HashMap<usize, u8>) -> Rgb {
+    match tile.tile_type {
+        TileType::Void => return BLACK,
+        TileType::Industry => return dim(INDUSTRY),
+        _ => {}
+    }
+    match tile.owner {
+        // Companies without a known colour use the colour of their slot
+        owner if owner < 15 => {
+            let colour = company_colours
+                .get(&usize::from(owner))
+                .copied()
+                .unwrap_or(owner);
+            COMPANY_COLOURS[usize::from(colour) % 16]
+        }
+        OWNER_TOWN => HOUSE,
+        OWNER_WATER => WATER,
+        _ if is_water(tile) => WATER,
+        _ => dim(LAND),
+    }
+}
+
+fn draw_vehicles(save_game: &SaveGame, map: &TileMap, minimap: &mut Minimap) {
+    const TILE_SIZE: i64 = 16;
+
+    for vehicle in save_game.items("VEHS") {
+        let Some(vehicle_type) = vehicle.integer("type") else {
+            continue;
+        };
+        let (key, colour) = match vehicle_type {
+            0 => ("train", VEHICLE_COLOURS[0]),
+            1 => ("roadveh", VEHICLE_COLOURS[1]),
+            2 => ("ship", VEHICLE_COLOURS[2]),
+            3 => ("aircraft", VEHICLE_COLOURS[3]),
+            _ => continue,
+        };
+        let Some(common) = vehicle
+            .child(key)
+            .and_then(|vehicle| vehicle.child("common"))
+        else {
+            continue;
+        };
+        let hidden = common
+            .integer("vehstatus")
+            .is_some_and(|status| has_bit(status as usize, VS_HIDDEN));
+        if let (false, Some(x), Some(y)) =
+            (hidden, common.integer("x_pos"), common.integer("y_pos"))
+        {
+            let (x, y) = (x / TILE_SIZE, y / TILE_SIZE);
+            if (0..i64::from(map.width())).contains(&x) && (0..i64::from(map.height())).contains(&y)
+            {
+                minimap.set_pixel(x as u32, y as u32, colour);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::common::OWNER_WATER;
+    use crate::map::{MapTile, TileType};
+    use crate::minimap::{
+        dim, render_minimap, Minimap, MinimapMode, Rgb, BLACK, COMPANY_COLOURS, HOUSE, INDUSTRY,
+        INDUSTRY_COLOURS, LAND, RAIL, VEHICLE_COLOURS, WATER,
+    };
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::tile::Tile;
+    use crate::vehicle::{Vehicle, VehicleType};
+
+    fn pixel(minimap: &Minimap, (x, y): (u32, u32)) -> Rgb {
+        let offset = (y as usize * minimap.width() as usize + x as usize) * 4;
+        let pixel = &minimap.pixels()[offset..offset + 4];
+        assert_eq!(pixel[3], 0xff);
+        [pixel[0], pixel[1], pixel[2]]
+    }
+
+    // The position of the first tile matching `predicate`, scanning rows from the north corner
+    fn find_tile(save_game: &SaveGame, predicate: impl Fn(&MapTile) -> bool) -> (u32, u32) {
+        let map = save_game.tile_map().unwrap();
+        (0..map.height())
+            .flat_map(|y| (0..map.width()).map(move |x| (x, y)))
+            .find(|(x, y)| predicate(&map.tile(*x, *y).unwrap()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_render_minimap() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+
+        for mode in [
+            MinimapMode::Contours,
+            MinimapMode::Vehicles,
+            MinimapMode::Industries,
+            MinimapMode::Routes,
+            MinimapMode::Vegetation,
+            MinimapMode::Owners,
+        ] {
+            let minimap = render_minimap(&save_game, mode).unwrap();
+            assert_eq!(
+                minimap.pixels().len(),
+                minimap.width() as usize * minimap.height() as usize * 4
+            );
+        }
+
+        let contours = render_minimap(&save_game, MinimapMode::Contours).unwrap();
+        let png = contours.encode_png().unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn test_minimap_colours() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+
+        let void = (0, 0);
+        let sea = find_tile(&save_game, |tile| {
+            matches!(Tile::from(tile), Tile::Water { .. }) && tile.owner == OWNER_WATER
+        });
+        let rail = find_tile(&save_game, |tile| {
+            matches!(Tile::from(tile), Tile::Railway { .. }) && tile.owner < 15
+        });
+        let house = find_tile(&save_game, |tile| tile.tile_type == TileType::House);
+        let (industry, industry_index) = {
+            let position = find_tile(&save_game, |tile| tile.tile_type == TileType::Industry);
+            let map = save_game.tile_map().unwrap();
+            match map.decode_tile(position.0, position.1) {
+                Some(Tile::Industry { industry, .. }) => (position, usize::from(industry)),
+                _ => unreachable!(),
+            }
+        };
+        let rail_owner = save_game
+            .tile_map()
+            .unwrap()
+            .tile(rail.0, rail.1)
+            .unwrap()
+            .owner;
+
+        let contours = render_minimap(&save_game, MinimapMode::Contours).unwrap();
+        assert_eq!(pixel(&contours, void), BLACK);
+        assert_eq!(pixel(&contours, sea), WATER);
+        assert_eq!(pixel(&contours, rail), RAIL);
+
+        let vehicles = render_minimap(&save_game, MinimapMode::Vehicles).unwrap();
+        assert_eq!(pixel(&vehicles, void), dim(BLACK));
+        assert_eq!(pixel(&vehicles, sea), dim(WATER));
+
+        let industries = render_minimap(&save_game, MinimapMode::Industries).unwrap();
+        let industry_type = save_game
+            .items("INDY")
+            .iter()
+            .find(|item| item.index == industry_index)
+            .and_then(|item| item.integer("type"))
+            .unwrap();
+        assert_eq!(
+            pixel(&industries, industry),
+            INDUSTRY_COLOURS[industry_type as usize]
+        );
+        assert_eq!(pixel(&industries, sea), dim(WATER));
+        assert_eq!(pixel(&industries, house), dim(LAND));
+
+        let routes = render_minimap(&save_game, MinimapMode::Routes).unwrap();
+        assert_eq!(pixel(&routes, rail), RAIL);
+        assert_eq!(pixel(&routes, house), dim(LAND));
+
+        let vegetation = render_minimap(&save_game, MinimapMode::Vegetation).unwrap();
+        assert_eq!(pixel(&vegetation, sea), WATER);
+        assert_eq!(pixel(&vegetation, house), HOUSE);
+        assert_eq!(pixel(&vegetation, industry), INDUSTRY);
+
+        let owners = render_minimap(&save_game, MinimapMode::Owners).unwrap();
+        let rail_colour = save_game
+            .items("PLYR")
+            .iter()
+            .find(|item| item.index == usize::from(rail_owner))
+            .and_then(|item| item.integer("colour"))
+            .unwrap();
+        assert_eq!(pixel(&owners, void), BLACK);
+        assert_eq!(pixel(&owners, sea), WATER);
+        assert_eq!(pixel(&owners, house), HOUSE);
+        assert_eq!(pixel(&owners, industry), dim(INDUSTRY));
+        assert_eq!(pixel(&owners, rail), COMPANY_COLOURS[rail_colour as usize]);
+    }
+
+    #[test]
+    fn test_minimap_vehicles() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let vehicles = save_game.vehicles().unwrap();
+        let drawn = vehicles
+            .iter()
+            .filter(|vehicle| !vehicle.hidden && vehicle.details.is_some())
+            .collect::<Vec<_>>();
+        let tile = |vehicle: &Vehicle| ((vehicle.x / 16) as u32, (vehicle.y / 16) as u32);
+
+        // A train that no vehicle drawn after it covers up
+        let (_, train) = drawn
+            .iter()
+            .enumerate()
+            .find(|(position, vehicle)| {
+                vehicle.vehicle_type == VehicleType::Train
+                    && drawn[position + 1..]
+                        .iter()
+                        .all(|other| tile(other) != tile(vehicle))
+            })
+            .unwrap();
+
+        let minimap = render_minimap(&save_game, MinimapMode::Vehicles).unwrap();
+        assert_eq!(pixel(&minimap, tile(train)), VEHICLE_COLOURS[0]);
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use crate::table_reader::TableItem;
+use crate::town_names::{generate_town_name, TownNameStyle, SPECSTR_TOWNNAME_START};
+
+// OpenTTD keeps the strings it stores in savegames at fixed ids, starting with the default
+// station names at STR_SV_STNAME
+const STR_SV_STNAME: i64 = 0x6006;
+const STR_SV_STNAME_BUOY: i64 = STR_SV_STNAME + 17;
+
+// The English default station names in string id order, `{}` being the town name
+const STATION_NAMES: &[&str] = &[
+    "{}",
+    "{} North",
+    "{} South",
+    "{} East",
+    "{} West",
+    "{} Central",
+    "{} Transfer",
+    "{} Halt",
+    "{} Valley",
+    "{} Heights",
+    "{} Woods",
+    "{} Lakeside",
+    "{} Exchange",
+    "{} Airport",
+    "{} Oilfield",
+    "{} Mines",
+    "{} Docks",
+    "{} Buoy",
+    "{} Waypoint",
+    "{} Annexe",
+    "{} Sidings",
+    "{} Branch",
+    "Upper {}",
+    "Lower {}",
+    "{} Heliport",
+    "{} Forest",
+];
+
+const FACIL_WAYPOINT: i64 = 1 << 7;
+
+// Generated company and president names are special string ids, with their seed stored next to
+// them. Company names derived from a town name follow the town name generators' order.
+const SPECSTR_SILLY_NAME: i64 = 0x70E5;
+const SPECSTR_ANDCO_NAME: i64 = 0x70E6;
+const SPECSTR_PRESIDENT_NAME: i64 = 0x70E7;
+const SPECSTR_COMPANY_NAME_START: i64 = 0x70EA;
+
+const SILLY_COMPANY_NAMES: &[&str] = &[
+    "Bloggs Brothers",
+    "Tiny Transport Ltd.",
+    "Express Travel",
+    "Comfy-Coach & Co.",
+    "Crush & Bump Ltd.",
+    "Broken & Late Ltd.",
+    "Sam Speedy & Son",
+    "Supersonic Travel",
+    "Mike's Motors",
+    "Lightning International",
+    "Pannik & Loozit Ltd.",
+    "Inter-City Transport",
+    "Getout & Pushit Ltd.",
+];
+
+const SURNAMES: &[&str] = &[
+    "Adams",
+    "Allan",
+    "Baker",
+    "Bigwig",
+    "Black",
+    "Bloggs",
+    "Brown",
+    "Campbell",
+    "Gordon",
+    "Hamilton",
+    "Hawthorn",
+    "Higgins",
+    "Green",
+    "Gribble",
+    "Jones",
+    "McAlpine",
+    "MacDonald",
+    "McIntosh",
+    "Muir",
+    "Murphy",
+    "Nelson",
+    "O'Donnell",
+    "Parker",
+    "Phillips",
+    "Pilkington",
+    "Quigley",
+    "Sharkey",
+    "Thomson",
+    "Watkins",
+];
+
+// Toyland presidents have names of their own
+const SILLY_SURNAMES: &[&str] = &[
+    "Grumpy", "Dozy", "Speedy", "Nosey", "Dribble", "Mushroom", "Cabbage", "Sniffle", "Fishy",
+    "Swindle", "Sneaky", "Nutkins",
+];
+
+const INITIALS: &[char] = &[
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'P', 'R', 'S', 'T', 'W',
+];
+
+// Towns without a custom name have one generated from `townnameparts`. Names of NewGRF town
+// name generators fall back to the town's number.
+pub fn town_name(town: &TableItem) -> String {
+    if let Some(name) = town.string("name") {
+        return String::from(name);
+    }
+    let generated = match (
+        town.integer("townnamegrfid"),
+        town.integer("townnametype")
+            .and_then(TownNameStyle::from_string_id),
+        town.integer("townnameparts"),
+    ) {
+        (None | Some(0), Some(style), Some(seed)) => Some(generate_town_name(style, seed as u32)),
+        _ => None,
+    };
+    generated.unwrap_or_else(|| format!("Town #{}", town.index))
+}
+
+// Companies are numbered from one in the game
+pub fn company_name(company: &TableItem, toyland: bool) -> String {
+    if let Some(name) = company.string("name") {
+        return String::from(name);
+    }
+    let seed = company.integer("name_2").unwrap_or_default() as u32;
+    let generated = match company.integer("name_1").unwrap_or_default() {
+        SPECSTR_SILLY_NAME => {
+            let index = (seed as usize & 0xFFFF).min(SILLY_COMPANY_NAMES.len() - 1);
+            Some(String::from(SILLY_COMPANY_NAMES[index]))
+        }
+        SPECSTR_ANDCO_NAME => Some(format!("{} & Co.", surname(seed, toyland))),
+        string_id => TownNameStyle::from_string_id(
+            string_id - SPECSTR_COMPANY_NAME_START + SPECSTR_TOWNNAME_START,
+        )
+        .map(|style| format!("{} Transport", generate_town_name(style, seed))),
+    };
+    generated.unwrap_or_else(|| format!("Company #{}", company.index + 1))
+}
+
+pub fn president_name(company: &TableItem, toyland: bool) -> String {
+    if let Some(name) = company.string("president_name") {
+        return String::from(name);
+    }
+    if company.integer("president_name_1") != Some(SPECSTR_PRESIDENT_NAME) {
+        return String::new();
+    }
+    let seed = company.integer("president_name_2").unwrap_or_default() as u32;
+
+    let mut name = format!(
+        "{}. ",
+        INITIALS[(INITIALS.len() * (seed & 0xFF) as usize) >> 8]
+    );
+    // The second initial is left out for most seeds
+    let second = ((INITIALS.len() + 35) * ((seed >> 8) & 0xFF) as usize) >> 8;
+    if let Some(second) = INITIALS.get(second) {
+        name += &format!("{}. ", second);
+    }
+    name + surname(seed, toyland)
+}
+
+fn surname(seed: u32, toyland: bool) -> &'static str {
+    let surnames = if toyland { SILLY_SURNAMES } else { SURNAMES };
+    surnames[(surnames.len() * ((seed >> 16) & 0xFF) as usize) >> 8]
+}
+
+// The fields shared by stations and waypoints, which tables nest inside the station kind
+pub(crate) fn base_station(station: &TableItem) -> Option<&TableItem> {
+    station
+        .child("normal")
+        .or_else(|| station.child("waypoint"))
+        .and_then(|station| station.child("base"))
+}
+
+pub fn station_name(station: &TableItem, towns: &HashMap<usize, &TableItem>) -> String {
+    let Some(base) = base_station(station) else {
+        return format!("Station #{}", station.index);
+    };
+    if let Some(name) = base.string("name") {
+        return String::from(name);
+    }
+
+    let town = base
+        .reference("town")
+        .and_then(|town| towns.get(&town))
+        .map(|town| town_name(town))
+        .unwrap_or_default();
+    let string_id = base.integer("string_id").unwrap_or_default();
+
+    if station.integer("facilities").unwrap_or_default() & FACIL_WAYPOINT != 0 {
+        let kind = if string_id == STR_SV_STNAME_BUOY {
+            "Buoy"
+        } else {
+            "Waypoint"
+        };
+        // Waypoints of the same town are numbered from the second one on
+        return match station
+            .child("waypoint")
+            .and_then(|wp| wp.integer("town_cn"))
+        {
+            Some(town_cn) if town_cn > 0 => format!("{} {} #{}", town, kind, town_cn + 1),
+            _ => format!("{} {}", town, kind),
+        };
+    }
+
+    match usize::try_from(string_id - STR_SV_STNAME)
+        .ok()
+        .and_then(|offset| STATION_NAMES.get(offset))
+    {
+        Some(template) => template.replace("{}", &town),
+        // Towns that ran out of names number their stations instead
+        None => format!("{} Station #{}", town, station.index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::names::{company_name, president_name, station_name};
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+
+    #[test]
+    fn test_default_station_names() {
+        let file = File::open("./test-big.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let towns = save_game.indexed_items("CITY");
+
+        let names = save_game
+            .items("STNN")
+            .iter()
+            .map(|station| station_name(station, &towns))
+            .collect::<Vec<_>>();
+        assert!(names.iter().any(|name| name.ends_with(" Central")));
+        assert!(names.iter().any(|name| name.contains(" Station #")));
+        assert!(names.iter().all(|name| !name.is_empty()));
+    }
+
+    #[test]
+    fn test_generated_company_names() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_game = SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap();
+        let company = &save_game.items("PLYR")[0];
+        assert_eq!(company.string("name"), None);
+        assert_eq!(company_name(company, false), "Murphy & Co.");
+        assert_eq!(president_name(company, false), "K. L. Murphy");
+    }
+}
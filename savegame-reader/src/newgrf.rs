@@ -0,0 +1,122 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::has_bit;
+use crate::error::{Result, SaveGameError};
+use crate::table_reader::TableItem;
+
+// The bit of GRFPalette telling which palette the NewGRF's sprites use
+const GRFP_USE_WINDOWS: u8 = 0;
+
+#[wasm_bindgen(typescript_custom_section)]
+const NEWGRF_TS: &'static str = r#"
+export type GrfPalette = "Dos" | "Windows";
+
+export interface NewGrfConfig {
+  filename: string;
+  grfid: number;
+  md5sum: string;
+  version?: number;
+  params: number[];
+  palette?: GrfPalette;
+}
+"#;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum GrfPalette {
+    Dos,
+    Windows,
+}
+
+// A NewGRF the savegame was played with, which has to be installed to load it. NewGRFs are matched
+// by their `grfid` and `md5sum`, the filename is where the file was found on the saving machine.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewGrfConfig {
+    pub filename: String,
+    pub grfid: u32,
+    // As lowercase hex digits, like in OpenTTD's NewGRF settings
+    pub md5sum: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    pub params: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub palette: Option<GrfPalette>,
+}
+
+impl NewGrfConfig {
+    pub fn from_item(item: &TableItem) -> Result<NewGrfConfig> {
+        let num_params = item.integer("num_params").unwrap_or_default() as usize;
+        Ok(NewGrfConfig {
+            filename: item
+                .string("filename")
+                .map(String::from)
+                .ok_or(SaveGameError::InvalidField(String::from("filename")))?,
+            grfid: item.field("ident.grfid")?,
+            md5sum: item
+                .integers("ident.md5sum")
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+            version: item.integer("version").map(|version| version as u32),
+            params: item
+                .integers("param")
+                .take(num_params)
+                .map(|param| param as u32)
+                .collect(),
+            palette: item.integer("palette").map(|palette| {
+                if has_bit(palette as usize, GRFP_USE_WINDOWS) {
+                    GrfPalette::Windows
+                } else {
+                    GrfPalette::Dos
+                }
+            }),
+        })
+    }
+
+    // The GRF ID as OpenTTD shows it, with the bytes in file order
+    pub fn grfid_hex(&self) -> String {
+        format!("{:08X}", self.grfid.swap_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::newgrf::GrfPalette;
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_newgrf_configs() {
+        let save_game = load("./test-big.sav");
+        let configs = save_game.newgrf_configs().unwrap();
+        assert_eq!(configs.len(), 11);
+        assert!(configs.iter().all(|config| config.md5sum.len() == 32));
+
+        let town_set = configs
+            .iter()
+            .find(|config| config.filename.ends_with("ttrs3w.grf"))
+            .unwrap();
+        assert_eq!(town_set.version, Some(44));
+        assert_eq!(town_set.params.len(), 4);
+        assert_eq!(town_set.palette, Some(GrfPalette::Windows));
+
+        let db_set = configs
+            .iter()
+            .find(|config| config.filename == "dbsetxlw.grf")
+            .unwrap();
+        assert_eq!(db_set.grfid_hex(), "6D620401");
+        assert_eq!(db_set.md5sum, "c5acb6d5536a464fd78871d91840a35f");
+    }
+
+    #[test]
+    fn test_newgrf_configs_without_newgrfs() {
+        let save_game = load("./test_v196.sav");
+        assert!(save_game.newgrf_configs().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,657 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::has_bit;
+use crate::error::{Result, SaveGameError};
+use crate::save_file::SaveVersion;
+use crate::table_reader::TableItem;
+use crate::vehicle::Vehicle;
+
+// Full load orders waited for any cargo to be fully loaded before this version
+const SLV_22: u16 = 22;
+// Order flags got their current layout in this version, OpenTTD converts older ones after loading
+const SLV_93: u16 = 93;
+// Vehicles shared their orders through order lists since this version, before that they referred
+// to their first order
+const SLV_105: u16 = 105;
+
+// OrderType
+const OT_GOTO_STATION: u8 = 1;
+const OT_GOTO_DEPOT: u8 = 2;
+const OT_GOTO_WAYPOINT: u8 = 6;
+const OT_CONDITIONAL: u8 = 7;
+const OT_IMPLICIT: u8 = 8;
+
+// OrderDepotTypeFlags and OrderDepotActionFlags
+const ODTFB_SERVICE: u8 = 0;
+const ODATFB_HALT: u8 = 3;
+const ODATFB_NEAREST_DEPOT: u8 = 4;
+const ODATFB_UNBUNCH: u8 = 5;
+
+// OrderFlagBits from before SLV_93
+const OFB_TRANSFER: u8 = 0;
+const OFB_UNLOAD: u8 = 1;
+const OFB_FULL_LOAD: u8 = 2;
+
+// Special values of the refit cargo and speed limit
+const CARGO_NO_REFIT: u8 = 0xFE;
+const NO_MAX_SPEED: u16 = u16::MAX;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ORDER_TS: &'static str = r#"
+export type NonStop = "StopEverywhere" | "NoStopAtIntermediate" | "NoStopAtDestination" | "NoStopAtAny" | "Unknown";
+export type StopLocation = "NearEnd" | "Middle" | "FarEnd";
+export type LoadType = "Normal" | "FullLoad" | "FullLoadAny" | "NoLoad" | "Unknown";
+export type UnloadType = "Normal" | "Unload" | "Transfer" | "NoUnload";
+export type ConditionVariable = "LoadPercentage" | "Reliability" | "MaxSpeed" | "Age" | "RequiresService" | "Unconditionally" | "RemainingLifetime" | "MaxReliability";
+export type ConditionComparator = "Equals" | "NotEquals" | "LessThan" | "LessEquals" | "MoreThan" | "MoreEquals" | "IsTrue" | "IsFalse";
+
+export type OrderKind =
+  | { type: "GoToStation"; station: number; non_stop: NonStop; stop_location: StopLocation; load: LoadType; unload: UnloadType }
+  | { type: "GoToDepot"; depot?: number; non_stop: NonStop; service_only: boolean; halt: boolean; unbunch: boolean }
+  | { type: "GoToWaypoint"; waypoint: number; non_stop: NonStop }
+  | { type: "Conditional"; skip_to: number; variable?: ConditionVariable; comparator?: ConditionComparator; value: number }
+  | { type: "Implicit"; station: number }
+  | { type: "Other"; order_type: number };
+
+export type Destination =
+  | { type: "Station"; station: number }
+  | { type: "Depot"; depot: number }
+  | { type: "Waypoint"; waypoint: number };
+
+export interface Order {
+  index: number;
+  kind: OrderKind;
+  wait_time: number;
+  travel_time: number;
+  max_speed?: number;
+  refit_cargo?: number;
+}
+
+export interface OrderList {
+  index: number;
+  orders: Order[];
+  vehicles: number[];
+  route: Destination[];
+}
+"#;
+
+// Orders from before SLV_93 are `Unknown`, as what their non-stop flag meant depended on a
+// setting
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum NonStop {
+    StopEverywhere,
+    NoStopAtIntermediate,
+    NoStopAtDestination,
+    NoStopAtAny,
+    Unknown,
+}
+
+// Where trains stop at a platform, other vehicles always stop at the far end
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum StopLocation {
+    NearEnd,
+    Middle,
+    FarEnd,
+}
+
+// Full load orders from before SLV_93 are `Unknown` unless they're older than SLV_22, as whether
+// they waited for any cargo depended on a setting
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum LoadType {
+    Normal,
+    FullLoad,
+    FullLoadAny,
+    NoLoad,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum UnloadType {
+    Normal,
+    Unload,
+    Transfer,
+    NoUnload,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum ConditionVariable {
+    LoadPercentage,
+    Reliability,
+    MaxSpeed,
+    Age,
+    RequiresService,
+    Unconditionally,
+    RemainingLifetime,
+    MaxReliability,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum ConditionComparator {
+    Equals,
+    NotEquals,
+    LessThan,
+    LessEquals,
+    MoreThan,
+    MoreEquals,
+    IsTrue,
+    IsFalse,
+}
+
+// Depots of aircraft are hangars, so their `depot` is the index of the airport's station.
+// Orders to the nearest depot have no depot of their own.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum OrderKind {
+    GoToStation {
+        station: usize,
+        non_stop: NonStop,
+        stop_location: StopLocation,
+        load: LoadType,
+        unload: UnloadType,
+    },
+    GoToDepot {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        depot: Option<usize>,
+        non_stop: NonStop,
+        service_only: bool,
+        halt: bool,
+        unbunch: bool,
+    },
+    GoToWaypoint {
+        waypoint: usize,
+        non_stop: NonStop,
+    },
+    // Jumps to the order at `skip_to` when the condition holds
+    Conditional {
+        skip_to: u8,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        variable: Option<ConditionVariable>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        comparator: Option<ConditionComparator>,
+        value: u16,
+    },
+    // Stations a vehicle stopped at without being ordered to, which the game adds by itself
+    Implicit {
+        station: usize,
+    },
+    Other {
+        order_type: u8,
+    },
+}
+
+// Where a vehicle is sent to by one of its orders
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum Destination {
+    Station { station: usize },
+    Depot { depot: usize },
+    Waypoint { waypoint: usize },
+}
+
+// Timetabled times are in ticks, zero if the order isn't timetabled
+#[derive(Debug, Clone, Serialize)]
+pub struct Order {
+    pub index: usize,
+    pub kind: OrderKind,
+    pub wait_time: u32,
+    pub travel_time: u32,
+    // In the internal speed units of the vehicle type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_speed: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refit_cargo: Option<u8>,
+}
+
+// The orders a group of vehicles share, `vehicles` being the front parts of their consists. The
+// route leaves out the orders that don't send a vehicle anywhere.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderList {
+    pub index: usize,
+    pub orders: Vec<Order>,
+    pub vehicles: Vec<usize>,
+    pub route: Vec<Destination>,
+}
+
+// The orders of a vehicle that was sold in a depot, kept to restore them when a new vehicle is
+// bought there
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderBackup {
+    pub index: usize,
+    pub user: u32,
+    pub tile: u32,
+    pub group: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clone: Option<usize>,
+    pub orders: Vec<Order>,
+}
+
+// Parts of other chunks orders refer to
+pub struct OrderContext<'a> {
+    pub version: SaveVersion,
+    pub orders: HashMap<usize, &'a TableItem>,
+}
+
+impl NonStop {
+    fn from_bits(bits: u8) -> NonStop {
+        match bits & 0x3 {
+            0 => NonStop::StopEverywhere,
+            1 => NonStop::NoStopAtIntermediate,
+            2 => NonStop::NoStopAtDestination,
+            _ => NonStop::NoStopAtAny,
+        }
+    }
+}
+
+impl StopLocation {
+    fn from_bits(bits: u8) -> StopLocation {
+        match bits & 0x3 {
+            0 => StopLocation::NearEnd,
+            1 => StopLocation::Middle,
+            _ => StopLocation::FarEnd,
+        }
+    }
+}
+
+impl LoadType {
+    fn from_bits(bits: u8) -> LoadType {
+        match bits & 0x7 {
+            2 => LoadType::FullLoad,
+            3 => LoadType::FullLoadAny,
+            4 => LoadType::NoLoad,
+            _ => LoadType::Normal,
+        }
+    }
+}
+
+impl UnloadType {
+    fn from_bits(bits: u8) -> UnloadType {
+        match bits & 0x7 {
+            1 => UnloadType::Unload,
+            2 => UnloadType::Transfer,
+            4 => UnloadType::NoUnload,
+            _ => UnloadType::Normal,
+        }
+    }
+}
+
+impl ConditionVariable {
+    fn from_bits(bits: u16) -> Option<ConditionVariable> {
+        Some(match bits {
+            0 => ConditionVariable::LoadPercentage,
+            1 => ConditionVariable::Reliability,
+            2 => ConditionVariable::MaxSpeed,
+            3 => ConditionVariable::Age,
+            4 => ConditionVariable::RequiresService,
+            5 => ConditionVariable::Unconditionally,
+            6 => ConditionVariable::RemainingLifetime,
+            7 => ConditionVariable::MaxReliability,
+            _ => return None,
+        })
+    }
+}
+
+impl ConditionComparator {
+    fn from_bits(bits: u8) -> Option<ConditionComparator> {
+        Some(match bits {
+            0 => ConditionComparator::Equals,
+            1 => ConditionComparator::NotEquals,
+            2 => ConditionComparator::LessThan,
+            3 => ConditionComparator::LessEquals,
+            4 => ConditionComparator::MoreThan,
+            5 => ConditionComparator::MoreEquals,
+            6 => ConditionComparator::IsTrue,
+            7 => ConditionComparator::IsFalse,
+            _ => return None,
+        })
+    }
+}
+
+impl OrderKind {
+    // The order type is in the lower bits of `type`, the upper ones hold where to stop
+    fn from_fields(order_type: u8, flags: u8, dest: u16) -> OrderKind {
+        let non_stop = NonStop::from_bits(order_type >> 6);
+        let destination = usize::from(dest);
+        match order_type & 0xF {
+            OT_GOTO_STATION => OrderKind::GoToStation {
+                station: destination,
+                non_stop,
+                stop_location: StopLocation::from_bits(order_type >> 4),
+                load: LoadType::from_bits(flags >> 4),
+                unload: UnloadType::from_bits(flags),
+            },
+            OT_GOTO_DEPOT => {
+                let flag = |n| has_bit(usize::from(flags), n);
+                let nearest = flag(ODATFB_NEAREST_DEPOT);
+                OrderKind::GoToDepot {
+                    depot: (!nearest).then_some(destination),
+                    non_stop,
+                    service_only: flag(ODTFB_SERVICE),
+                    halt: flag(ODATFB_HALT),
+                    unbunch: flag(ODATFB_UNBUNCH),
+                }
+            }
+            OT_GOTO_WAYPOINT => OrderKind::GoToWaypoint {
+                waypoint: destination,
+                non_stop,
+            },
+            OT_CONDITIONAL => OrderKind::Conditional {
+                skip_to: flags,
+                variable: ConditionVariable::from_bits(dest >> 11),
+                comparator: ConditionComparator::from_bits(order_type >> 5),
+                value: dest & 0x7FF,
+            },
+            OT_IMPLICIT => OrderKind::Implicit {
+                station: destination,
+            },
+            order_type => OrderKind::Other { order_type },
+        }
+    }
+
+    // Converts orders from before SLV_93 like OpenTTD's ConvertFromOldSavegame(), which only
+    // kept the order type in `type`
+    fn from_old_fields(order_type: u8, flags: u8, dest: u16, version: SaveVersion) -> OrderKind {
+        let flag = |n| has_bit(usize::from(flags), n);
+        let destination = usize::from(dest);
+        match order_type & 0xF {
+            OT_GOTO_STATION => OrderKind::GoToStation {
+                station: destination,
+                non_stop: NonStop::Unknown,
+                stop_location: StopLocation::FarEnd,
+                load: if flag(OFB_UNLOAD) {
+                    LoadType::NoLoad
+                } else if !flag(OFB_FULL_LOAD) {
+                    LoadType::Normal
+                } else if version.is_before(SLV_22) {
+                    LoadType::FullLoadAny
+                } else {
+                    LoadType::Unknown
+                },
+                unload: if flag(OFB_TRANSFER) {
+                    UnloadType::Transfer
+                } else if flag(OFB_UNLOAD) {
+                    UnloadType::Unload
+                } else {
+                    UnloadType::Normal
+                },
+            },
+            // Depot orders used the unload and full load flags for their action
+            OT_GOTO_DEPOT => OrderKind::GoToDepot {
+                depot: Some(destination),
+                non_stop: NonStop::Unknown,
+                service_only: flags & 0x6 == 0x6,
+                halt: flags & 0x6 == 0x4,
+                unbunch: false,
+            },
+            OT_GOTO_WAYPOINT => OrderKind::GoToWaypoint {
+                waypoint: destination,
+                non_stop: NonStop::Unknown,
+            },
+            _ => OrderKind::from_fields(order_type & 0xF, 0, dest),
+        }
+    }
+}
+
+impl Order {
+    pub fn from_item(item: &TableItem, version: SaveVersion) -> Result<Order> {
+        let (order_type, flags, dest) = (
+            item.field("type")?,
+            item.field("flags")?,
+            item.field("dest")?,
+        );
+        let kind = if version.is_before(SLV_93) {
+            OrderKind::from_old_fields(order_type, flags, dest, version)
+        } else {
+            OrderKind::from_fields(order_type, flags, dest)
+        };
+        Ok(Order {
+            index: item.index,
+            kind,
+            // Stored as 32 bits since SLV_TIMETABLE_TICKS_TYPE
+            wait_time: item.integer("wait_time").unwrap_or_default() as u32,
+            travel_time: item.integer("travel_time").unwrap_or_default() as u32,
+            max_speed: item
+                .integer("max_speed")
+                .map(|speed| speed as u16)
+                .filter(|speed| *speed != NO_MAX_SPEED),
+            refit_cargo: item
+                .integer("refit_cargo")
+                .map(|cargo| cargo as u8)
+                .filter(|cargo| *cargo < CARGO_NO_REFIT),
+        })
+    }
+
+    pub fn destination(&self) -> Option<Destination> {
+        match self.kind {
+            OrderKind::GoToStation { station, .. } => Some(Destination::Station { station }),
+            OrderKind::GoToDepot {
+                depot: Some(depot), ..
+            } => Some(Destination::Depot { depot }),
+            OrderKind::GoToWaypoint { waypoint, .. } => Some(Destination::Waypoint { waypoint }),
+            _ => None,
+        }
+    }
+}
+
+// Follows the orders linked from `first`, stopping at a loop in case of a corrupt chain
+fn order_chain(first: Option<usize>, context: &OrderContext) -> Result<Vec<Order>> {
+    let mut orders = vec![];
+    let mut visited = HashSet::new();
+    let mut next = first;
+    while let Some(index) = next.filter(|index| visited.insert(*index)) {
+        let item = context
+            .orders
+            .get(&index)
+            .ok_or(SaveGameError::InvalidField(String::from("next")))?;
+        orders.push(Order::from_item(item, context.version)?);
+        next = item.reference("next");
+    }
+    Ok(orders)
+}
+
+impl OrderList {
+    pub fn from_item(
+        item: &TableItem,
+        vehicles: Vec<usize>,
+        context: &OrderContext,
+    ) -> Result<OrderList> {
+        OrderList::new(item.index, item.reference("first"), vehicles, context)
+    }
+
+    fn new(
+        index: usize,
+        first: Option<usize>,
+        vehicles: Vec<usize>,
+        context: &OrderContext,
+    ) -> Result<OrderList> {
+        let orders = order_chain(first, context)?;
+        Ok(OrderList {
+            index,
+            route: orders.iter().filter_map(Order::destination).collect(),
+            orders,
+            vehicles,
+        })
+    }
+
+    // Builds the order lists with the vehicles sharing them. Savegames from before order lists
+    // get one list per first order, numbered like that order.
+    pub fn build(
+        lists: &[TableItem],
+        vehicles: &[Vehicle],
+        context: &OrderContext,
+    ) -> Result<Vec<OrderList>> {
+        let mut shared: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for vehicle in vehicles {
+            if let Some(orders) = vehicle.details.as_ref().and_then(|details| details.orders) {
+                shared.entry(orders).or_default().push(vehicle.index);
+            }
+        }
+
+        if context.version.is_before(SLV_105) {
+            return shared
+                .into_iter()
+                .map(|(first, vehicles)| OrderList::new(first, Some(first), vehicles, context))
+                .collect();
+        }
+        lists
+            .iter()
+            .map(|item| {
+                let vehicles = shared.remove(&item.index).unwrap_or_default();
+                OrderList::from_item(item, vehicles, context)
+            })
+            .collect()
+    }
+}
+
+impl OrderBackup {
+    pub fn from_item(item: &TableItem, context: &OrderContext) -> Result<OrderBackup> {
+        Ok(OrderBackup {
+            index: item.index,
+            user: item.field("user")?,
+            tile: item.field("tile")?,
+            group: item.field("group")?,
+            clone: item.reference("clone"),
+            orders: order_chain(item.reference("orders"), context)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::order::{
+        ConditionComparator, ConditionVariable, Destination, LoadType, NonStop, OrderKind,
+        StopLocation, UnloadType,
+    };
+    use crate::save_file::{CompressedSaveFile, SaveVersion};
+    use crate::save_game::SaveGame;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_order_kinds() {
+        assert_eq!(
+            OrderKind::from_fields(0x61, 0x30, 12),
+            OrderKind::GoToStation {
+                station: 12,
+                non_stop: NonStop::NoStopAtIntermediate,
+                stop_location: StopLocation::FarEnd,
+                load: LoadType::FullLoadAny,
+                unload: UnloadType::Normal,
+            }
+        );
+        // Service at the nearest depot
+        assert_eq!(
+            OrderKind::from_fields(0x02, 0x11, 0),
+            OrderKind::GoToDepot {
+                depot: None,
+                non_stop: NonStop::StopEverywhere,
+                service_only: true,
+                halt: false,
+                unbunch: false,
+            }
+        );
+        // Skip to order 3 when the load is below 50%
+        assert_eq!(
+            OrderKind::from_fields(0x47, 3, 50),
+            OrderKind::Conditional {
+                skip_to: 3,
+                variable: Some(ConditionVariable::LoadPercentage),
+                comparator: Some(ConditionComparator::LessThan),
+                value: 50,
+            }
+        );
+    }
+
+    #[test]
+    fn test_old_order_kinds() {
+        let version = SaveVersion::new(50, 0);
+        // A transfer with full load, which the full_load_any setting decided the meaning of
+        assert_eq!(
+            OrderKind::from_old_fields(0x01, 0x05, 12, version),
+            OrderKind::GoToStation {
+                station: 12,
+                non_stop: NonStop::Unknown,
+                stop_location: StopLocation::FarEnd,
+                load: LoadType::Unknown,
+                unload: UnloadType::Transfer,
+            }
+        );
+        assert_eq!(
+            OrderKind::from_old_fields(0x01, 0x04, 12, SaveVersion::new(21, 0)),
+            OrderKind::GoToStation {
+                station: 12,
+                non_stop: NonStop::Unknown,
+                stop_location: StopLocation::FarEnd,
+                load: LoadType::FullLoadAny,
+                unload: UnloadType::Normal,
+            }
+        );
+        assert_eq!(
+            OrderKind::from_old_fields(0x01, 0x02, 12, version),
+            OrderKind::GoToStation {
+                station: 12,
+                non_stop: NonStop::Unknown,
+                stop_location: StopLocation::FarEnd,
+                load: LoadType::NoLoad,
+                unload: UnloadType::Unload,
+            }
+        );
+        // Service at depot 3
+        assert_eq!(
+            OrderKind::from_old_fields(0x02, 0x06, 3, version),
+            OrderKind::GoToDepot {
+                depot: Some(3),
+                non_stop: NonStop::Unknown,
+                service_only: true,
+                halt: false,
+                unbunch: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_order_lists_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let lists = save_game.order_lists().unwrap();
+        assert_eq!(lists.len(), 175);
+        assert_eq!(
+            lists.iter().map(|list| list.orders.len()).sum::<usize>(),
+            763
+        );
+
+        let stations = save_game.stations().unwrap();
+        let routes = lists.iter().flat_map(|list| &list.route);
+        assert!(routes.clone().count() > 0);
+        assert!(routes
+            .filter_map(|destination| match destination {
+                Destination::Station { station } => Some(station),
+                _ => None,
+            })
+            .all(|station| stations.iter().any(|s| s.index == *station)));
+        assert!(lists.iter().all(|list| !list.vehicles.is_empty()));
+    }
+
+    #[test]
+    fn test_order_lists_from_tables() {
+        let save_game = load("./test-big.sav");
+        let lists = save_game.order_lists().unwrap();
+        assert_eq!(lists.len(), 245);
+        assert_eq!(
+            lists.iter().map(|list| list.orders.len()).sum::<usize>(),
+            1186
+        );
+        let orders = lists.iter().flat_map(|list| &list.orders);
+        assert!(orders
+            .clone()
+            .any(|order| matches!(order.kind, OrderKind::GoToWaypoint { .. })));
+        assert!(orders.clone().any(|order| order.wait_time > 0));
+        assert!(save_game.order_backups().unwrap().is_empty());
+    }
+}
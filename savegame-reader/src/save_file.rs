@@ -1,16 +1,68 @@
 use std::fs::File;
-use std::io;
-use std::io::{BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::{fmt, io};
 
 use byteorder::ReadBytesExt;
-use lzma_rs::xz_decompress;
+use flate2::read::ZlibDecoder;
+use lzma_rust2::XzReader;
+use serde::Serialize;
 
 use crate::common::has_bit;
+use crate::error::{Result, SaveGameError};
+
+// Savegame versions that changed the layout of data this crate reads, named like OpenTTD's
+// `SaveLoadVersion` entries
+pub const SLV_69: u16 = 69;
+pub const SLV_183: u16 = 183;
+pub const SLV_194: u16 = 194;
+pub const SLV_RIFF_TO_ARRAY: u16 = 294;
+pub const SLV_TABLE_CHUNKS: u16 = 295;
+pub const SLV_LINKGRAPH_TRAVEL_TIME: u16 = 297;
+pub const SLV_LINKGRAPH_EDGES: u16 = 304;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+pub struct SaveVersion {
+    pub major: u16,
+    pub minor: u8,
+}
+
+impl SaveVersion {
+    pub const fn new(major: u16, minor: u8) -> SaveVersion {
+        SaveVersion { major, minor }
+    }
+
+    // The version is stored as a big endian u16 followed by the minor version and a padding byte
+    fn from_header(bytes: [u8; 4]) -> SaveVersion {
+        SaveVersion {
+            major: u16::from_be_bytes([bytes[0], bytes[1]]),
+            minor: bytes[2],
+        }
+    }
+
+    pub fn is_before(&self, major: u16) -> bool {
+        self.major < major
+    }
+
+    pub fn is_before_minor(&self, major: u16, minor: u8) -> bool {
+        self.major < major || (self.major == major && self.minor < minor)
+    }
+}
+
+impl fmt::Display for SaveVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
 
 pub trait SaveFile: Read {
     fn debug_info(&mut self) -> String;
 
-    fn read_gamma(&mut self) -> io::Result<usize> {
+    fn version(&self) -> SaveVersion;
+
+    fn position(&self) -> u64;
+
+    fn read_gamma(&mut self) -> Result<usize> {
+        let offset = self.position();
         let mut length = usize::from(self.read_u8()?);
         if has_bit(length, 7) {
             length &= !0b1000_0000;
@@ -21,7 +73,7 @@ pub trait SaveFile: Read {
                     if has_bit(length, 4) {
                         length &= !0b0001_0000;
                         if has_bit(length, 3) {
-                            panic!("Unsupported array length")
+                            return Err(SaveGameError::BadGamma { offset });
                         }
                         length = length << 8 | usize::from(self.read_u8()?);
                     }
@@ -34,59 +86,71 @@ pub trait SaveFile: Read {
         Ok(length)
     }
 
-    fn read_string(&mut self) -> io::Result<String> {
+    fn read_string(&mut self) -> Result<String> {
         let length = self.read_gamma()?;
+        let offset = self.position();
         let mut buf = vec![0; length];
         self.read_exact(&mut buf)?;
 
-        Ok(String::from(std::str::from_utf8(&buf).unwrap()))
+        String::from_utf8(buf).map_err(|_| SaveGameError::InvalidUtf8 { offset })
     }
 }
 
 pub struct CompressedSaveFile<'a> {
-    pub version: u32,
+    pub version: SaveVersion,
     pub reader: Box<dyn Read + 'a>,
+    position: u64,
 }
 
 impl<'a> CompressedSaveFile<'a> {
-    pub fn new<R: Read + 'a>(mut reader: R) -> CompressedSaveFile<'a> {
-        let format = SaveFileFormat::read_from(&mut reader);
+    pub fn new<R: Read + 'a>(mut reader: R) -> Result<CompressedSaveFile<'a>> {
+        let format = SaveFileFormat::read_from(&mut reader)?;
 
         let mut version = [0; 4];
-        reader.read_exact(&mut version).unwrap();
-        let version = u32::from_be_bytes(version) >> 16;
-
-        let mut buf = Vec::new();
-        reader.read_to_end(&mut buf).unwrap();
-
-        let mut decompressed_buf = Vec::new();
-        xz_decompress(&mut buf.as_slice(), &mut decompressed_buf).unwrap();
+        reader
+            .read_exact(&mut version)
+            .map_err(|_| SaveGameError::Truncated { offset: 4 })?;
+        let version = SaveVersion::from_header(version);
 
-        let reader = match format {
-            SaveFileFormat::Lzma => BufReader::new(Cursor::new(decompressed_buf)),
-            format => panic!("Unsupported savegame format: {:?}", format),
+        // Decompress on demand so only the block being decoded is held in memory
+        let reader: Box<dyn Read + 'a> = match format {
+            SaveFileFormat::Lzma => Box::new(XzReader::new(BufReader::new(reader), false)),
+            SaveFileFormat::Lzo => {
+                // Savegames from before versioning wrote the block headers in native (little) endian
+                let big_endian = version.major != 0;
+                Box::new(LzoReader::new(BufReader::new(reader), big_endian))
+            }
+            SaveFileFormat::Uncompressed => Box::new(BufReader::new(reader)),
+            SaveFileFormat::Zlib => Box::new(ZlibDecoder::new(reader)),
         };
 
-        CompressedSaveFile {
+        Ok(CompressedSaveFile {
             version,
-            reader: Box::new(reader),
-        }
+            reader,
+            position: 0,
+        })
     }
 
     pub fn debug_info(&mut self) -> String {
         String::from("No debug info available")
     }
 
-    pub fn debug_save_decoded(&mut self, file: &mut File) {
+    pub fn debug_save_decoded(&mut self, file: &mut File) -> io::Result<()> {
         let mut v = Vec::new();
-        self.reader.read_to_end(&mut v).unwrap();
-        file.write(v.as_slice()).unwrap();
+        self.reader.read_to_end(&mut v)?;
+        file.write_all(v.as_slice())
     }
 }
 
 impl<'a> Read for CompressedSaveFile<'a> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.reader.read(buf)
+        // Each decompressor reports malformed input differently, so report them all as invalid data
+        let read = self.reader.read(buf).map_err(|err| match err.kind() {
+            io::ErrorKind::UnexpectedEof | io::ErrorKind::Interrupted => err,
+            _ => io::Error::new(io::ErrorKind::InvalidData, err.to_string()),
+        })?;
+        self.position += read as u64;
+        Ok(read)
     }
 }
 
@@ -94,16 +158,29 @@ impl<'a> SaveFile for CompressedSaveFile<'a> {
     fn debug_info(&mut self) -> String {
         String::from("No debug info available")
     }
+
+    fn version(&self) -> SaveVersion {
+        self.version
+    }
+
+    fn position(&self) -> u64 {
+        self.position
+    }
 }
 
 pub struct DebugSaveFile<R: Read + Seek> {
+    pub version: SaveVersion,
     pub reader: Box<R>,
+    position: u64,
 }
 
 impl<R: Read + Seek> DebugSaveFile<R> {
-    pub fn new_from_decoded(reader: R) -> DebugSaveFile<R> {
+    // Decoded files have no header, so the version they were saved with has to be passed in
+    pub fn new_from_decoded(reader: R, version: SaveVersion) -> DebugSaveFile<R> {
         DebugSaveFile {
+            version,
             reader: Box::new(reader),
+            position: 0,
         }
     }
 
@@ -117,13 +194,16 @@ impl<R: Read + Seek> DebugSaveFile<R> {
 
 impl<R: Read + Seek> Read for DebugSaveFile<R> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.reader.read(buf)
+        let read = self.reader.read(buf)?;
+        self.position += read as u64;
+        Ok(read)
     }
 }
 
 impl<R: Read + Seek> Seek for DebugSaveFile<R> {
     fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
-        self.reader.seek(pos)
+        self.position = self.reader.seek(pos)?;
+        Ok(self.position)
     }
 }
 
@@ -134,30 +214,459 @@ impl<R: Read + Seek> SaveFile for DebugSaveFile<R> {
             self.reader.stream_position().unwrap()
         )
     }
+
+    fn version(&self) -> SaveVersion {
+        self.version
+    }
+
+    fn position(&self) -> u64 {
+        self.position
+    }
 }
 
 #[derive(Debug)]
 enum SaveFileFormat {
+    Uncompressed,
     Lzo,
     Zlib,
     Lzma,
-    Unknown,
 }
 
 impl SaveFileFormat {
-    fn read_from(reader: &mut impl Read) -> SaveFileFormat {
+    fn read_from(reader: &mut impl Read) -> Result<SaveFileFormat> {
         let mut format = [0; 4];
-        reader.read_exact(&mut format).unwrap();
+        reader
+            .read_exact(&mut format)
+            .map_err(|_| SaveGameError::Truncated { offset: 0 })?;
 
         SaveFileFormat::from_bytes(&format)
     }
 
-    fn from_bytes(bytes: &[u8; 4]) -> SaveFileFormat {
-        match std::str::from_utf8(bytes).unwrap() {
-            "OTTD" => SaveFileFormat::Lzo,
-            "OTTZ" => SaveFileFormat::Zlib,
-            "OTTX" => SaveFileFormat::Lzma,
-            _ => SaveFileFormat::Unknown,
+    fn from_bytes(bytes: &[u8; 4]) -> Result<SaveFileFormat> {
+        match bytes {
+            b"OTTN" => Ok(SaveFileFormat::Uncompressed),
+            b"OTTD" => Ok(SaveFileFormat::Lzo),
+            b"OTTZ" => Ok(SaveFileFormat::Zlib),
+            b"OTTX" => Ok(SaveFileFormat::Lzma),
+            unknown => Err(SaveGameError::UnknownFormat(*unknown)),
         }
     }
 }
+
+struct LzoReader<R: Read> {
+    inner: R,
+    big_endian: bool,
+    block: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> LzoReader<R> {
+    fn new(inner: R, big_endian: bool) -> LzoReader<R> {
+        LzoReader {
+            inner,
+            big_endian,
+            block: Vec::with_capacity(LZO_BUFFER_SIZE),
+            position: 0,
+        }
+    }
+}
+
+impl<R: Read> Read for LzoReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.block.len() {
+            self.block.clear();
+            self.position = 0;
+            let has_block = read_lzo_block(&mut self.inner, self.big_endian, &mut self.block)
+                .map_err(|err| match err {
+                    SaveGameError::Io(err) => err,
+                    SaveGameError::Decompression(reason) => {
+                        io::Error::new(io::ErrorKind::InvalidData, reason)
+                    }
+                    err => io::Error::new(io::ErrorKind::InvalidData, err.to_string()),
+                })?;
+            if !has_block {
+                return Ok(0);
+            }
+        }
+
+        let read = buf.len().min(self.block.len() - self.position);
+        buf[..read].copy_from_slice(&self.block[self.position..self.position + read]);
+        self.position += read;
+        Ok(read)
+    }
+}
+
+const LZO_BUFFER_SIZE: usize = 8192;
+const LZO_MAX_BLOCK_SIZE: usize = LZO_BUFFER_SIZE + LZO_BUFFER_SIZE / 16 + 64 + 3;
+
+/// Reads one block of an LZO container, verifies its checksum and appends the decompressed data
+/// to `out`. Returns `false` once the compressed stream is exhausted.
+fn read_lzo_block(reader: &mut impl Read, big_endian: bool, out: &mut Vec<u8>) -> Result<bool> {
+    let mut header = [0; 8];
+    let mut read = 0;
+    while read < header.len() {
+        match reader.read(&mut header[read..])? {
+            0 if read == 0 => return Ok(false),
+            0 => {
+                return Err(SaveGameError::Decompression(String::from(
+                    "truncated LZO block header",
+                )))
+            }
+            n => read += n,
+        }
+    }
+
+    let (checksum, size) = if big_endian {
+        (
+            u32::from_be_bytes([header[0], header[1], header[2], header[3]]),
+            u32::from_be_bytes([header[4], header[5], header[6], header[7]]),
+        )
+    } else {
+        (
+            u32::from_le_bytes([header[0], header[1], header[2], header[3]]),
+            u32::from_le_bytes([header[4], header[5], header[6], header[7]]),
+        )
+    };
+    let size = size as usize;
+    if size > LZO_MAX_BLOCK_SIZE {
+        return Err(SaveGameError::Decompression(format!(
+            "inconsistent LZO block size {}",
+            size
+        )));
+    }
+
+    // The checksum covers the size field as it is stored in the file followed by the block
+    let mut block = vec![0; size + 4];
+    block[..4].copy_from_slice(&header[4..]);
+    reader
+        .read_exact(&mut block[4..])
+        .map_err(|_| SaveGameError::Decompression(String::from("truncated LZO block")))?;
+    if lzo_adler32(0, &block) != checksum {
+        return Err(SaveGameError::Decompression(String::from(
+            "bad LZO block checksum",
+        )));
+    }
+
+    let block_start = out.len();
+    lzo1x_decompress(&block[4..], out).map_err(SaveGameError::Decompression)?;
+    if out.len() - block_start > LZO_BUFFER_SIZE {
+        return Err(SaveGameError::Decompression(String::from(
+            "LZO block too large",
+        )));
+    }
+    Ok(true)
+}
+
+fn lzo_adler32(adler: u32, data: &[u8]) -> u32 {
+    const BASE: u32 = 65521;
+    let mut s1 = adler & 0xFFFF;
+    let mut s2 = adler >> 16;
+    // 5552 is the largest n for which the sums cannot overflow before being reduced
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            s1 += u32::from(*byte);
+            s2 += s1;
+        }
+        s1 %= BASE;
+        s2 %= BASE;
+    }
+    s2 << 16 | s1
+}
+
+struct LzoInput<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> LzoInput<'a> {
+    fn byte(&mut self) -> std::result::Result<usize, String> {
+        let byte = *self
+            .data
+            .get(self.position)
+            .ok_or_else(|| String::from("LZO input overrun"))?;
+        self.position += 1;
+        Ok(usize::from(byte))
+    }
+
+    fn le16(&mut self) -> std::result::Result<usize, String> {
+        Ok(self.byte()? | self.byte()? << 8)
+    }
+
+    // Lengths that don't fit into their instruction are continued with zero bytes worth 255 each
+    fn extended_length(&mut self, base: usize) -> std::result::Result<usize, String> {
+        let mut length = base;
+        loop {
+            match self.byte()? {
+                0 => length += 255,
+                byte => return Ok(length + byte),
+            }
+        }
+    }
+
+    fn copy_literals(
+        &mut self,
+        count: usize,
+        out: &mut Vec<u8>,
+    ) -> std::result::Result<(), String> {
+        let literals = self
+            .data
+            .get(self.position..self.position + count)
+            .ok_or_else(|| String::from("LZO input overrun"))?;
+        out.extend_from_slice(literals);
+        self.position += count;
+        Ok(())
+    }
+}
+
+fn copy_match(
+    out: &mut Vec<u8>,
+    block_start: usize,
+    distance: usize,
+    length: usize,
+) -> std::result::Result<(), String> {
+    if distance > out.len() - block_start {
+        return Err(String::from("LZO lookbehind overrun"));
+    }
+    // Matches may overlap the bytes they produce, so copy byte by byte
+    let start = out.len() - distance;
+    for i in 0..length {
+        out.push(out[start + i]);
+    }
+    Ok(())
+}
+
+/// Decompresses a single LZO1X block (the format written by `lzo1x_1_compress`) onto `out`.
+fn lzo1x_decompress(input: &[u8], out: &mut Vec<u8>) -> std::result::Result<(), String> {
+    let mut input = LzoInput {
+        data: input,
+        position: 0,
+    };
+    let block_start = out.len();
+    // Number of literals copied by the previous instruction, capped at 4. It determines how
+    // instructions 0..15 are interpreted.
+    let mut state = 0;
+
+    if input.data.first().is_some_and(|first| *first > 17) {
+        let count = input.byte()? - 17;
+        input.copy_literals(count, out)?;
+        state = count.min(4);
+    }
+
+    loop {
+        let instruction = input.byte()?;
+        let trailing_literals = match instruction {
+            0..=15 if state == 0 => {
+                let length = match instruction {
+                    0 => input.extended_length(15)?,
+                    length => length,
+                } + 3;
+                input.copy_literals(length, out)?;
+                state = 4;
+                continue;
+            }
+            0..=15 => {
+                let distance = (input.byte()? << 2) + (instruction >> 2) + 1;
+                if state == 4 {
+                    copy_match(out, block_start, distance + 2048, 3)?;
+                } else {
+                    copy_match(out, block_start, distance, 2)?;
+                }
+                instruction & 3
+            }
+            16..=31 => {
+                let length = match instruction & 7 {
+                    0 => input.extended_length(7)?,
+                    length => length,
+                } + 2;
+                let trailer = input.le16()?;
+                let distance = 16384 + ((instruction & 8) << 11) + (trailer >> 2);
+                if distance == 16384 {
+                    return if input.position == input.data.len() {
+                        Ok(())
+                    } else {
+                        Err(String::from("LZO input not consumed"))
+                    };
+                }
+                copy_match(out, block_start, distance, length)?;
+                trailer & 3
+            }
+            32..=63 => {
+                let length = match instruction & 31 {
+                    0 => input.extended_length(31)?,
+                    length => length,
+                } + 2;
+                let trailer = input.le16()?;
+                copy_match(out, block_start, (trailer >> 2) + 1, length)?;
+                trailer & 3
+            }
+            _ => {
+                let length = (instruction >> 5) + 1;
+                let distance = (input.byte()? << 3) + ((instruction >> 2) & 7) + 1;
+                copy_match(out, block_start, distance, length)?;
+                instruction & 3
+            }
+        };
+        input.copy_literals(trailing_literals, out)?;
+        state = trailing_literals;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::{Read, Write};
+
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+
+    use crate::error::SaveGameError;
+    use crate::loader::load_file;
+    use crate::save_file::{
+        lzo1x_decompress, lzo_adler32, CompressedSaveFile, SaveVersion, LZO_BUFFER_SIZE, SLV_194,
+        SLV_LINKGRAPH_EDGES,
+    };
+
+    fn decoded_empty_map() -> Vec<u8> {
+        let mut buf = Vec::new();
+        File::open("./test_empty_map.sav.decoded")
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        buf
+    }
+
+    fn with_header(tag: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut file = tag.to_vec();
+        file.extend_from_slice(&[0x01, 0x2e, 0x00, 0x00]);
+        file.extend_from_slice(payload);
+        file
+    }
+
+    #[test]
+    fn test_read_version() {
+        let file = with_header(b"OTTN", &[]);
+        let save_file = CompressedSaveFile::new(file.as_slice()).unwrap();
+        assert_eq!(save_file.version, SaveVersion::new(302, 0));
+
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_file = CompressedSaveFile::new(file).unwrap();
+        assert_eq!(save_file.version, SaveVersion::new(196, 0));
+        assert!(save_file.version.is_before(SLV_LINKGRAPH_EDGES));
+        assert!(!save_file.version.is_before(SLV_194));
+        assert!(SaveVersion::new(194, 1).is_before_minor(194, 2));
+    }
+
+    #[test]
+    fn test_read_zlib_file() {
+        let decoded = decoded_empty_map();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&decoded).unwrap();
+        let file = with_header(b"OTTZ", &encoder.finish().unwrap());
+
+        let mut save_file = CompressedSaveFile::new(file.as_slice()).unwrap();
+        let mut read_back = Vec::new();
+        save_file.read_to_end(&mut read_back).unwrap();
+
+        assert_eq!(save_file.version, SaveVersion::new(302, 0));
+        assert_eq!(read_back, decoded);
+    }
+
+    #[test]
+    fn test_read_uncompressed_file() {
+        let decoded = decoded_empty_map();
+        let file = with_header(b"OTTN", &decoded);
+
+        let save_file = CompressedSaveFile::new(file.as_slice()).unwrap();
+        let chunks = load_file(save_file).unwrap();
+        assert!(chunks.contains_key("LGRP"));
+    }
+
+    #[test]
+    fn test_read_corrupt_zlib_file() {
+        let file = with_header(b"OTTZ", b"definitely not zlib");
+        let save_file = CompressedSaveFile::new(file.as_slice()).unwrap();
+        assert!(matches!(
+            load_file(save_file),
+            Err(SaveGameError::Decompression(_))
+        ));
+    }
+
+    // Encodes data as LZO blocks that only consist of a single literal run
+    fn lzo_store(data: &[u8]) -> Vec<u8> {
+        let mut container = Vec::new();
+        for chunk in data.chunks(LZO_BUFFER_SIZE) {
+            let mut block = Vec::new();
+            if chunk.len() <= 238 {
+                block.push(17 + chunk.len() as u8);
+            } else {
+                block.push(0);
+                let mut remaining = chunk.len() - 3 - 15;
+                while remaining > 255 {
+                    block.push(0);
+                    remaining -= 255;
+                }
+                block.push(remaining as u8);
+            }
+            block.extend_from_slice(chunk);
+            block.extend_from_slice(&[0x11, 0x00, 0x00]);
+
+            let mut checked = (block.len() as u32).to_be_bytes().to_vec();
+            checked.extend_from_slice(&block);
+            container.extend_from_slice(&lzo_adler32(0, &checked).to_be_bytes());
+            container.extend_from_slice(&checked);
+        }
+        container
+    }
+
+    #[test]
+    fn test_lzo1x_decompress() {
+        // "hello" as initial literals, a short and a long back reference, then the end marker
+        let block = [
+            22, b'h', b'e', b'l', b'l', b'o', 80, 0, 42, 16, 0, 0x11, 0, 0,
+        ];
+        let mut out = Vec::new();
+        lzo1x_decompress(&block, &mut out).unwrap();
+        assert_eq!(out, b"hellohellohellohello");
+    }
+
+    #[test]
+    fn test_lzo1x_decompress_rejects_bad_distance() {
+        let block = [
+            22,
+            b'h',
+            b'e',
+            b'l',
+            b'l',
+            b'o',
+            32 | 8,
+            0xFF,
+            0x00,
+            0x11,
+            0,
+            0,
+        ];
+        assert!(lzo1x_decompress(&block, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_read_lzo_file() {
+        let decoded = decoded_empty_map();
+        let file = with_header(b"OTTD", &lzo_store(&decoded));
+
+        let save_file = CompressedSaveFile::new(file.as_slice()).unwrap();
+        let chunks = load_file(save_file).unwrap();
+        assert!(chunks.contains_key("LGRP"));
+    }
+
+    #[test]
+    fn test_read_lzo_file_with_bad_checksum() {
+        let mut container = lzo_store(b"some savegame data");
+        container[0] ^= 0xFF;
+        let file = with_header(b"OTTD", &container);
+        let save_file = CompressedSaveFile::new(file.as_slice()).unwrap();
+        assert!(matches!(
+            load_file(save_file),
+            Err(SaveGameError::Decompression(_))
+        ));
+    }
+}
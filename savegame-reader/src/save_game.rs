@@ -0,0 +1,219 @@
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+
+use crate::cargo::{climate, CargoTable, Climate};
+use crate::company::{Company, CompanyContext, CompanyInfrastructure};
+use crate::consist::{build_consists, Consist};
+use crate::engine::{Engine, EngineContext};
+use crate::error::Result;
+use crate::industry::{Industry, IndustryContext};
+use crate::line::{build_lines, Line};
+use crate::link_graph::{LinkGraph, NodeResolver};
+use crate::loader::{load_file, Chunk};
+use crate::map::{map_size, take_layers, TileMap};
+use crate::newgrf::NewGrfConfig;
+use crate::order::{OrderBackup, OrderContext, OrderList};
+use crate::save_file::{SaveFile, SaveVersion};
+use crate::station::{Station, StationContext};
+use crate::table_reader::TableItem;
+use crate::town::{Town, TownContext, TownHouses};
+use crate::vehicle::{Vehicle, VehicleContext};
+
+#[derive(Debug)]
+pub struct SaveGame {
+    pub version: SaveVersion,
+    pub chunks: HashMap<String, Chunk>,
+    // The map layer chunks, kept apart from `chunks` until the tile map takes them over
+    map_layers: RefCell<HashMap<String, Chunk>>,
+    // Decoding the map layers is expensive, so they are only decoded once on first use
+    tile_map: OnceCell<TileMap>,
+}
+
+impl SaveGame {
+    pub fn load(save_file: impl SaveFile) -> Result<SaveGame> {
+        let version = save_file.version();
+        let mut chunks = load_file(save_file)?;
+        Ok(SaveGame {
+            version,
+            map_layers: RefCell::new(take_layers(&mut chunks)),
+            chunks,
+            tile_map: OnceCell::new(),
+        })
+    }
+
+    // The decoded items of a chunk, empty if the chunk is missing or could not be decoded
+    pub fn items(&self, chunk_id: &str) -> &[TableItem] {
+        self.chunks
+            .get(chunk_id)
+            .and_then(Chunk::items)
+            .unwrap_or_default()
+    }
+
+    pub fn link_graphs(&self) -> Result<Vec<LinkGraph>> {
+        let resolver = NodeResolver {
+            map_width: self.map_size()?.0,
+            stations: self
+                .stations()?
+                .into_iter()
+                .map(|station| (station.index, station))
+                .collect(),
+        };
+        self.items("LGRP")
+            .iter()
+            .map(|item| LinkGraph::from_item(item, self.version, &resolver))
+            .collect()
+    }
+
+    pub fn stations(&self) -> Result<Vec<Station>> {
+        let context = StationContext {
+            map_width: self.map_size()?.0,
+            towns: self.indexed_items("CITY"),
+            packets: self.packet_counts(),
+        };
+        self.items("STNN")
+            .iter()
+            .map(|item| Station::from_item(item, &context))
+            .collect()
+    }
+
+    pub fn vehicles(&self) -> Result<Vec<Vehicle>> {
+        let context = VehicleContext {
+            version: self.version,
+            packets: self.packet_counts(),
+        };
+        self.items("VEHS")
+            .iter()
+            .map(|item| Vehicle::from_item(item, &context))
+            .collect()
+    }
+
+    pub fn consists(&self) -> Result<Vec<Consist>> {
+        Ok(build_consists(&self.vehicles()?, &self.engines()?))
+    }
+
+    pub fn order_lists(&self) -> Result<Vec<OrderList>> {
+        OrderList::build(self.items("ORDL"), &self.vehicles()?, &self.order_context())
+    }
+
+    pub fn lines(&self) -> Result<Vec<Line>> {
+        let vehicles = self.vehicles()?;
+        let order_lists = OrderList::build(self.items("ORDL"), &vehicles, &self.order_context())?;
+        Ok(build_lines(
+            &order_lists,
+            &build_consists(&vehicles, &self.engines()?),
+            &self.stations()?,
+        ))
+    }
+
+    pub fn order_backups(&self) -> Result<Vec<OrderBackup>> {
+        let context = self.order_context();
+        self.items("BKOR")
+            .iter()
+            .map(|item| OrderBackup::from_item(item, &context))
+            .collect()
+    }
+
+    fn order_context(&self) -> OrderContext<'_> {
+        OrderContext {
+            version: self.version,
+            orders: self.indexed_items("ORDR"),
+        }
+    }
+
+    pub fn towns(&self) -> Result<Vec<Town>> {
+        let map = self.tile_map()?;
+        let context = TownContext {
+            version: self.version,
+            map_width: map.width(),
+            houses: TownHouses::count(map),
+        };
+        self.items("CITY")
+            .iter()
+            .map(|item| Town::from_item(item, &context))
+            .collect()
+    }
+
+    pub fn companies(&self) -> Result<Vec<Company>> {
+        let context = CompanyContext {
+            version: self.version,
+            toyland: self.climate() == Climate::Toyland,
+            infrastructure: CompanyInfrastructure::count(self.tile_map()?, self.version),
+        };
+        self.items("PLYR")
+            .iter()
+            .map(|item| Company::from_item(item, &context))
+            .collect()
+    }
+
+    pub fn industries(&self) -> Result<Vec<Industry>> {
+        let context = IndustryContext {
+            version: self.version,
+            map_width: self.map_size()?.0,
+            industry_mapping: self.indexed_items("IIDS"),
+        };
+        self.items("INDY")
+            .iter()
+            .map(|item| Industry::from_item(item, &context))
+            .collect()
+    }
+
+    pub fn engines(&self) -> Result<Vec<Engine>> {
+        let context = EngineContext {
+            version: self.version,
+            engine_mapping: self.indexed_items("EIDS"),
+        };
+        self.items("ENGN")
+            .iter()
+            .map(|item| Engine::from_item(item, &context))
+            .collect()
+    }
+
+    pub fn newgrf_configs(&self) -> Result<Vec<NewGrfConfig>> {
+        self.items("NGRF")
+            .iter()
+            .map(NewGrfConfig::from_item)
+            .collect()
+    }
+
+    // The cargo count of each cargo packet by its pool index
+    fn packet_counts(&self) -> HashMap<usize, u32> {
+        self.items("CAPA")
+            .iter()
+            .map(|packet| (packet.index, packet.integer("count").unwrap_or(0) as u32))
+            .collect()
+    }
+
+    // The items of a chunk by their pool index, for resolving references from other chunks
+    pub fn indexed_items(&self, chunk_id: &str) -> HashMap<usize, &TableItem> {
+        self.items(chunk_id)
+            .iter()
+            .map(|item| (item.index, item))
+            .collect()
+    }
+
+    pub fn map_size(&self) -> Result<(u32, u32)> {
+        map_size(&self.chunks)
+    }
+
+    pub fn climate(&self) -> Climate {
+        climate(self)
+    }
+
+    pub fn cargo_table(&self) -> CargoTable {
+        CargoTable::from_save_game(self)
+    }
+
+    pub fn tile_map(&self) -> Result<&TileMap> {
+        if let Some(map) = self.tile_map.get() {
+            return Ok(map);
+        }
+        let (width, height) = self.map_size()?;
+        let map = TileMap::from_layers(
+            width,
+            height,
+            &mut self.map_layers.borrow_mut(),
+            self.version,
+        )?;
+        Ok(self.tile_map.get_or_init(|| map))
+    }
+}
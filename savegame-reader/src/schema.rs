@@ -0,0 +1,392 @@
+use std::io::{Cursor, Read};
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::error::{Result, SaveGameError};
+use crate::loader::Chunk;
+use crate::save_file::{SaveFile, SaveVersion, SLV_183, SLV_69};
+use crate::table_reader::{DataType, ParsedField, ParsedFieldContent, ParsedFieldData, TableItem};
+
+mod cargo_packet;
+mod company;
+mod engine;
+mod industry;
+mod link_graph;
+mod newgrf;
+mod order;
+mod station;
+mod town;
+mod vehicle;
+
+// Savegames from before SLV_TABLE_CHUNKS store Array and SparseArray chunks without a header
+// describing their fields. The descriptions below mirror OpenTTD's SaveLoad tables so these
+// chunks can be decoded into the same shape as their table counterparts.
+
+pub(crate) const SL_MIN_VERSION: u16 = 0;
+pub(crate) const SL_MAX_VERSION: u16 = u16::MAX;
+
+const SLV_6: u16 = 6;
+const SLV_107: u16 = 107;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SchemaKind {
+    Var(DataType),
+    Arr(DataType, usize),
+    Deque(DataType),
+    Str,
+    Ref,
+    RefList,
+    Null(usize),
+}
+
+#[derive(Debug)]
+pub(crate) struct SchemaField {
+    key: &'static str,
+    kind: SchemaKind,
+    from: u16,
+    to: u16,
+}
+
+pub(crate) const fn var(key: &'static str, data_type: DataType) -> SchemaField {
+    cond_var(key, data_type, SL_MIN_VERSION, SL_MAX_VERSION)
+}
+
+pub(crate) const fn cond_var(
+    key: &'static str,
+    data_type: DataType,
+    from: u16,
+    to: u16,
+) -> SchemaField {
+    SchemaField {
+        key,
+        kind: SchemaKind::Var(data_type),
+        from,
+        to,
+    }
+}
+
+pub(crate) const fn cond_arr(
+    key: &'static str,
+    data_type: DataType,
+    length: usize,
+    from: u16,
+    to: u16,
+) -> SchemaField {
+    SchemaField {
+        key,
+        kind: SchemaKind::Arr(data_type, length),
+        from,
+        to,
+    }
+}
+
+pub(crate) const fn str(key: &'static str) -> SchemaField {
+    cond_str(key, SL_MIN_VERSION, SL_MAX_VERSION)
+}
+
+pub(crate) const fn cond_deque(
+    key: &'static str,
+    data_type: DataType,
+    from: u16,
+    to: u16,
+) -> SchemaField {
+    SchemaField {
+        key,
+        kind: SchemaKind::Deque(data_type),
+        from,
+        to,
+    }
+}
+
+pub(crate) const fn cond_str(key: &'static str, from: u16, to: u16) -> SchemaField {
+    SchemaField {
+        key,
+        kind: SchemaKind::Str,
+        from,
+        to,
+    }
+}
+
+pub(crate) const fn reference(key: &'static str) -> SchemaField {
+    cond_ref(key, SL_MIN_VERSION, SL_MAX_VERSION)
+}
+
+pub(crate) const fn cond_ref(key: &'static str, from: u16, to: u16) -> SchemaField {
+    SchemaField {
+        key,
+        kind: SchemaKind::Ref,
+        from,
+        to,
+    }
+}
+
+pub(crate) const fn cond_ref_list(key: &'static str, from: u16, to: u16) -> SchemaField {
+    SchemaField {
+        key,
+        kind: SchemaKind::RefList,
+        from,
+        to,
+    }
+}
+
+pub(crate) const fn null(length: usize, from: u16, to: u16) -> SchemaField {
+    SchemaField {
+        key: "",
+        kind: SchemaKind::Null(length),
+        from,
+        to,
+    }
+}
+
+/// Reads a single array item that has already been cut out of the chunk by its length prefix.
+pub(crate) struct ItemReader<'a> {
+    cursor: Cursor<&'a [u8]>,
+    offset: u64,
+    version: SaveVersion,
+}
+
+impl<'a> ItemReader<'a> {
+    pub(crate) fn new(data: &'a [u8], offset: u64, version: SaveVersion) -> ItemReader<'a> {
+        ItemReader {
+            cursor: Cursor::new(data),
+            offset,
+            version,
+        }
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.cursor.get_ref().len() - self.cursor.position() as usize
+    }
+
+    pub(crate) fn is_before(&self, major: u16) -> bool {
+        self.version.is_before(major)
+    }
+
+    pub(crate) fn read_reference(&mut self) -> Result<u32> {
+        Ok(if self.is_before(SLV_69) {
+            u32::from(self.read_u16::<BigEndian>()?)
+        } else {
+            self.read_u32::<BigEndian>()?
+        })
+    }
+
+    pub(crate) fn skip(&mut self, length: usize) -> Result<()> {
+        if length > self.remaining() {
+            return Err(SaveGameError::Truncated {
+                offset: self.position() + self.remaining() as u64,
+            });
+        }
+        self.cursor
+            .set_position(self.cursor.position() + length as u64);
+        Ok(())
+    }
+}
+
+impl<'a> Read for ItemReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.cursor.read(buf)
+    }
+}
+
+impl<'a> SaveFile for ItemReader<'a> {
+    fn debug_info(&mut self) -> String {
+        format!("Position in item: {}", self.cursor.position())
+    }
+
+    fn version(&self) -> SaveVersion {
+        self.version
+    }
+
+    fn position(&self) -> u64 {
+        self.offset + self.cursor.position()
+    }
+}
+
+impl SchemaField {
+    fn read_from(&self, reader: &mut ItemReader, fields: &mut Vec<ParsedField>) -> Result<()> {
+        if reader.is_before(self.from) || !reader.is_before(self.to) {
+            return Ok(());
+        }
+
+        let data = match self.kind {
+            SchemaKind::Null(length) => return reader.skip(length),
+            SchemaKind::Var(data_type) => ParsedFieldData::Scalar(data_type.read_from(reader)?),
+            SchemaKind::Arr(data_type, length) => ParsedFieldData::List(
+                (0..length)
+                    .map(|_| data_type.read_from(reader))
+                    .collect::<Result<_>>()?,
+            ),
+            SchemaKind::Deque(data_type) => {
+                let length = reader.read_u32::<BigEndian>()?;
+                ParsedFieldData::List(
+                    (0..length)
+                        .map(|_| data_type.read_from(reader))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            // Strings are stored like in tables: a list holding the string unless it is empty
+            SchemaKind::Str => match reader.read_string()? {
+                string if string.is_empty() => ParsedFieldData::List(vec![]),
+                string => ParsedFieldData::List(vec![ParsedFieldContent::String(string)]),
+            },
+            SchemaKind::Ref => {
+                ParsedFieldData::Scalar(ParsedFieldContent::U32(reader.read_reference()?))
+            }
+            SchemaKind::RefList => {
+                let length = reader.read_u32::<BigEndian>()?;
+                ParsedFieldData::List(
+                    (0..length)
+                        .map(|_| reader.read_reference().map(ParsedFieldContent::U32))
+                        .collect::<Result<_>>()?,
+                )
+            }
+        };
+        fields.push(ParsedField {
+            key: String::from(self.key),
+            data,
+        });
+        Ok(())
+    }
+}
+
+pub(crate) fn read_object(
+    reader: &mut ItemReader,
+    schema: &[SchemaField],
+) -> Result<Vec<ParsedField>> {
+    let mut fields = vec![];
+    for field in schema {
+        field.read_from(reader, &mut fields)?;
+    }
+    Ok(fields)
+}
+
+pub(crate) fn struct_list(key: &str, items: Vec<Vec<ParsedField>>) -> ParsedField {
+    ParsedField {
+        key: String::from(key),
+        data: ParsedFieldData::List(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, fields)| ParsedFieldContent::Struct(TableItem { index, fields }))
+                .collect(),
+        ),
+    }
+}
+
+pub(crate) fn read_struct_list(
+    reader: &mut ItemReader,
+    key: &str,
+    schema: &[SchemaField],
+    count: usize,
+) -> Result<ParsedField> {
+    let items = (0..count)
+        .map(|_| read_object(reader, schema))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(struct_list(key, items))
+}
+
+// Removes a field that only exists in the savegame to size a following list and returns its value
+pub(crate) fn take_count(fields: &mut Vec<ParsedField>, key: &str) -> usize {
+    let Some(position) = fields.iter().position(|field| field.key == key) else {
+        return 0;
+    };
+    match fields.remove(position).data {
+        ParsedFieldData::Scalar(ParsedFieldContent::U8(count)) => usize::from(count),
+        ParsedFieldData::Scalar(ParsedFieldContent::U16(count)) => usize::from(count),
+        ParsedFieldData::Scalar(ParsedFieldContent::U32(count)) => count as usize,
+        _ => 0,
+    }
+}
+
+pub(crate) type ItemDecoder = fn(&mut ItemReader) -> Result<Vec<ParsedField>>;
+
+// Chunks from before SLV_TABLE_CHUNKS that have a known layout
+pub(crate) fn decoder_for(chunk_id: &str, version: SaveVersion) -> Option<ItemDecoder> {
+    match chunk_id {
+        "BKOR" => Some(order::read_order_backup),
+        "CAPA" => Some(cargo_packet::read_cargo_packet),
+        "CITY" => Some(town::read_town),
+        "EIDS" => Some(engine::read_engine_id_mapping),
+        "ENGN" => Some(engine::read_engine),
+        "IIDS" => Some(industry::read_entity_mapping),
+        "INDY" => Some(industry::read_industry),
+        "NGRF" => Some(newgrf::read_grf_config),
+        "ORDL" => Some(order::read_order_list),
+        "STNN" => Some(station::read_station),
+        "VEHS" => Some(vehicle::read_vehicle),
+        "ORDR" if !version.is_before(SLV_6) => Some(order::read_order),
+        "PLYR" if !version.is_before(SLV_107) => Some(company::read_company),
+        "LGRP" if !version.is_before(SLV_183) => Some(link_graph::read_link_graph),
+        _ => None,
+    }
+}
+
+/// Reads the items of an Array or SparseArray chunk, decoding them with `decoder` when the chunk
+/// has a known schema. Without one the items are skipped using their length prefixes.
+pub(crate) fn read_array(
+    save_file: &mut impl SaveFile,
+    sparse: bool,
+    decoder: Option<ItemDecoder>,
+) -> Result<Chunk> {
+    let mut items = vec![];
+    let mut raw_items = 0usize;
+    let mut raw_bytes = 0usize;
+    let mut array_index = 0usize;
+    loop {
+        let length = save_file.read_gamma()?;
+        if length == 0 {
+            break;
+        }
+        let mut length = length - 1;
+
+        let index = if sparse {
+            let index_offset = save_file.position();
+            let index = save_file.read_gamma()?;
+            // The sparse index is part of the item length
+            length = length
+                .checked_sub((save_file.position() - index_offset) as usize)
+                .ok_or(SaveGameError::Corrupt {
+                    offset: index_offset,
+                    reason: String::from("sparse array item shorter than its index"),
+                })?;
+            index
+        } else {
+            array_index += 1;
+            array_index - 1
+        };
+        if length == 0 {
+            continue;
+        }
+
+        let item_offset = save_file.position();
+        let mut buf = vec![0; length];
+        save_file.read_exact(&mut buf)?;
+
+        if let Some(decoder) = decoder {
+            let mut reader = ItemReader::new(&buf, item_offset, save_file.version());
+            let fields = decoder(&mut reader)?;
+            if reader.remaining() != 0 {
+                return Err(SaveGameError::Corrupt {
+                    offset: item_offset,
+                    reason: format!(
+                        "item {} has {} bytes left after decoding {} bytes",
+                        index,
+                        reader.remaining(),
+                        length - reader.remaining()
+                    ),
+                });
+            }
+            items.push(TableItem { index, fields });
+        }
+        raw_items += 1;
+        raw_bytes += length;
+    }
+
+    Ok(match decoder {
+        Some(_) => Chunk::Table(items),
+        None => Chunk::Raw {
+            items: raw_items,
+            bytes: raw_bytes,
+        },
+    })
+}
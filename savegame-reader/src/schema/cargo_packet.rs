@@ -0,0 +1,23 @@
+use crate::error::Result;
+use crate::schema::{cond_var, null, read_object, var, ItemReader, SchemaField, SL_MAX_VERSION};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+const SLV_121: u16 = 121;
+const SLV_125: u16 = 125;
+
+static CARGO_PACKET_DESC: &[SchemaField] = &[
+    var("source", U16),
+    var("source_xy", U32),
+    var("loaded_at_xy", U32),
+    var("count", U16),
+    var("days_in_transit", U8),
+    var("feeder_share", I64),
+    cond_var("source_type", U8, SLV_125, SL_MAX_VERSION),
+    cond_var("source_id", U16, SLV_125, SL_MAX_VERSION),
+    null(1, 0, SLV_121),
+];
+
+pub(crate) fn read_cargo_packet(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, CARGO_PACKET_DESC)
+}
@@ -0,0 +1,144 @@
+use crate::error::Result;
+use crate::schema::{
+    cond_arr, cond_ref, cond_str, cond_var, null, read_object, read_struct_list, take_count, var,
+    ItemReader, SchemaField, SL_MAX_VERSION, SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+const SLV_EXTEND_CARGOTYPES: u16 = 199;
+
+// Liveries were added over time, see OpenTTD's LiveryScheme
+const LS_END: usize = 23;
+
+static COMPANY_DESC: &[SchemaField] = &[
+    var("name_2", U32),
+    var("name_1", StringId),
+    cond_str("name", 84, SL_MAX_VERSION),
+    var("president_name_1", StringId),
+    var("president_name_2", U32),
+    cond_str("president_name", 84, SL_MAX_VERSION),
+    var("face", U32),
+    cond_var("money", I32, SL_MIN_VERSION, 1),
+    cond_var("money", I64, 1, SL_MAX_VERSION),
+    cond_var("current_loan", I32, SL_MIN_VERSION, 65),
+    cond_var("current_loan", I64, 65, SL_MAX_VERSION),
+    var("colour", U8),
+    var("money_fraction", U8),
+    null(1, SL_MIN_VERSION, 58),
+    var("block_preview", U8),
+    null(2, SL_MIN_VERSION, 94),
+    null(4, 94, 170),
+    cond_var("location_of_HQ", U16, SL_MIN_VERSION, 6),
+    cond_var("location_of_HQ", U32, 6, SL_MAX_VERSION),
+    cond_var("last_build_coordinate", U16, SL_MIN_VERSION, 6),
+    cond_var("last_build_coordinate", U32, 6, SL_MAX_VERSION),
+    cond_var("inaugurated_year", U8, SL_MIN_VERSION, 31),
+    cond_var("inaugurated_year", I32, 31, SL_MAX_VERSION),
+    cond_arr("share_owners", U8, 4, SL_MIN_VERSION, SL_MAX_VERSION),
+    var("num_valid_stat_ent", U8),
+    var("months_of_bankruptcy", U8),
+    cond_var("bankrupt_asked", U8, SL_MIN_VERSION, 104),
+    cond_var("bankrupt_asked", U16, 104, SL_MAX_VERSION),
+    var("bankrupt_timeout", I16),
+    cond_var("bankrupt_value", I32, SL_MIN_VERSION, 65),
+    cond_var("bankrupt_value", I64, 65, SL_MAX_VERSION),
+    cond_arr("yearly_expenses", I32, 3 * 13, SL_MIN_VERSION, 2),
+    cond_arr("yearly_expenses", I64, 3 * 13, 2, SL_MAX_VERSION),
+    cond_var("is_ai", I8, 2, SL_MAX_VERSION),
+    null(1, 107, 112),
+    null(1, 4, 100),
+    cond_var("terraform_limit", U32, 156, SL_MAX_VERSION),
+    cond_var("clear_limit", U32, 156, SL_MAX_VERSION),
+    cond_var("tree_limit", U32, 175, SL_MAX_VERSION),
+];
+
+static COMPANY_SETTINGS_DESC: &[SchemaField] = &[
+    null(512, 16, 19),
+    cond_ref("engine_renew_list", 19, SL_MAX_VERSION),
+    cond_var("settings.engine_renew", I8, 16, SL_MAX_VERSION),
+    cond_var("settings.engine_renew_months", I16, 16, SL_MAX_VERSION),
+    cond_var("settings.engine_renew_money", U32, 16, SL_MAX_VERSION),
+    cond_var("settings.renew_keep_length", I8, 2, SL_MAX_VERSION),
+    cond_var(
+        "settings.vehicle.servint_ispercent",
+        I8,
+        120,
+        SL_MAX_VERSION,
+    ),
+    cond_var("settings.vehicle.servint_trains", U16, 120, SL_MAX_VERSION),
+    cond_var("settings.vehicle.servint_roadveh", U16, 120, SL_MAX_VERSION),
+    cond_var(
+        "settings.vehicle.servint_aircraft",
+        U16,
+        120,
+        SL_MAX_VERSION,
+    ),
+    cond_var("settings.vehicle.servint_ships", U16, 120, SL_MAX_VERSION),
+    null(63, 2, 144),
+];
+
+static COMPANY_ECONOMY_DESC: &[SchemaField] = &[
+    cond_var("income", I32, SL_MIN_VERSION, 2),
+    cond_var("income", I64, 2, SL_MAX_VERSION),
+    cond_var("expenses", I32, SL_MIN_VERSION, 2),
+    cond_var("expenses", I64, 2, SL_MAX_VERSION),
+    cond_var("company_value", I32, SL_MIN_VERSION, 2),
+    cond_var("company_value", I64, 2, SL_MAX_VERSION),
+    cond_arr("delivered_cargo", I32, 1, SL_MIN_VERSION, 170),
+    cond_arr("delivered_cargo", U32, 32, 170, SLV_EXTEND_CARGOTYPES),
+    cond_arr(
+        "delivered_cargo",
+        U32,
+        64,
+        SLV_EXTEND_CARGOTYPES,
+        SL_MAX_VERSION,
+    ),
+    var("performance_history", I32),
+];
+
+static COMPANY_LIVERY_DESC: &[SchemaField] = &[
+    cond_var("in_use", U8, 34, SL_MAX_VERSION),
+    cond_var("colour1", U8, 34, SL_MAX_VERSION),
+    cond_var("colour2", U8, 34, SL_MAX_VERSION),
+];
+
+// Savegames from before SLV_107 keep the state of the old AI after AI companies, which isn't
+// decoded, so the chunk is only read from that version on
+pub(crate) fn read_company(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    let mut company = read_object(reader, COMPANY_DESC)?;
+    let num_valid_stat_ent = take_count(&mut company, "num_valid_stat_ent");
+    company.push(read_struct_list(
+        reader,
+        "settings",
+        COMPANY_SETTINGS_DESC,
+        1,
+    )?);
+    company.push(read_struct_list(
+        reader,
+        "cur_economy",
+        COMPANY_ECONOMY_DESC,
+        1,
+    )?);
+    company.push(read_struct_list(
+        reader,
+        "old_economy",
+        COMPANY_ECONOMY_DESC,
+        num_valid_stat_ent,
+    )?);
+
+    let num_liveries = if reader.is_before(63) {
+        LS_END - 4
+    } else if reader.is_before(85) {
+        LS_END - 2
+    } else {
+        LS_END
+    };
+    company.push(read_struct_list(
+        reader,
+        "liveries",
+        COMPANY_LIVERY_DESC,
+        num_liveries,
+    )?);
+    Ok(company)
+}
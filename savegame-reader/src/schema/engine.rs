@@ -0,0 +1,49 @@
+use crate::error::Result;
+use crate::schema::{
+    cond_str, cond_var, null, read_object, var, ItemReader, SchemaField, SL_MAX_VERSION,
+    SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+static ENGINE_DESC: &[SchemaField] = &[
+    cond_var("intro_date", U16, SL_MIN_VERSION, 31),
+    cond_var("intro_date", I32, 31, SL_MAX_VERSION),
+    cond_var("age", U16, SL_MIN_VERSION, 31),
+    cond_var("age", I32, 31, SL_MAX_VERSION),
+    var("reliability", U16),
+    var("reliability_spd_dec", U16),
+    var("reliability_start", U16),
+    var("reliability_max", U16),
+    var("reliability_final", U16),
+    var("duration_phase_1", U16),
+    var("duration_phase_2", U16),
+    var("duration_phase_3", U16),
+    null(1, SL_MIN_VERSION, 121),
+    var("flags", U8),
+    null(1, SL_MIN_VERSION, 179),
+    cond_var("preview_asked", U16, 179, SL_MAX_VERSION),
+    cond_var("preview_company", U8, 179, SL_MAX_VERSION),
+    var("preview_wait", U8),
+    null(1, SL_MIN_VERSION, 45),
+    cond_var("company_avail", U8, SL_MIN_VERSION, 104),
+    cond_var("company_avail", U16, 104, SL_MAX_VERSION),
+    cond_var("company_hidden", U16, 193, SL_MAX_VERSION),
+    cond_str("name", 84, SL_MAX_VERSION),
+    null(16, 2, 144),
+];
+
+static ENGINE_ID_MAPPING_DESC: &[SchemaField] = &[
+    var("grfid", U32),
+    var("internal_id", U16),
+    var("type", U8),
+    var("substitute_id", U8),
+];
+
+pub(crate) fn read_engine(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, ENGINE_DESC)
+}
+
+pub(crate) fn read_engine_id_mapping(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, ENGINE_ID_MAPPING_DESC)
+}
@@ -0,0 +1,217 @@
+use crate::error::Result;
+use crate::schema::{
+    cond_arr, cond_ref, cond_str, cond_var, null, read_object, reference, var, ItemReader,
+    SchemaField, SL_MAX_VERSION, SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+const SLV_EXTEND_INDUSTRY_CARGO_SLOTS: u16 = 202;
+const SLV_SERVE_NEUTRAL_INDUSTRIES: u16 = 210;
+const SLV_GS_INDUSTRY_CONTROL: u16 = 287;
+const SLV_INDUSTRY_TEXT: u16 = 289;
+
+const INDUSTRY_NUM_INPUTS: usize = 16;
+const INDUSTRY_NUM_OUTPUTS: usize = 16;
+
+static INDUSTRY_DESC: &[SchemaField] = &[
+    cond_var("location.tile", U16, SL_MIN_VERSION, 6),
+    cond_var("location.tile", U32, 6, SL_MAX_VERSION),
+    var("location.w", U8),
+    var("location.h", U8),
+    reference("town"),
+    cond_ref(
+        "neutral_station",
+        SLV_SERVE_NEUTRAL_INDUSTRIES,
+        SL_MAX_VERSION,
+    ),
+    null(2, SL_MIN_VERSION, 61),
+    cond_arr("produced_cargo", U8, 2, 78, SLV_EXTEND_INDUSTRY_CARGO_SLOTS),
+    cond_arr(
+        "produced_cargo",
+        U8,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "incoming_cargo_waiting",
+        U16,
+        3,
+        70,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "incoming_cargo_waiting",
+        U16,
+        INDUSTRY_NUM_INPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "produced_cargo_waiting",
+        U16,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "produced_cargo_waiting",
+        U16,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "production_rate",
+        U8,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "production_rate",
+        U8,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    null(3, SL_MIN_VERSION, 61),
+    cond_arr("accepts_cargo", U8, 3, 78, SLV_EXTEND_INDUSTRY_CARGO_SLOTS),
+    cond_arr(
+        "accepts_cargo",
+        U8,
+        INDUSTRY_NUM_INPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    var("prod_level", U8),
+    cond_arr(
+        "this_month_production",
+        U16,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "this_month_production",
+        U16,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "this_month_transported",
+        U16,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "this_month_transported",
+        U16,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "last_month_pct_transported",
+        U8,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "last_month_pct_transported",
+        U8,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "last_month_production",
+        U16,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "last_month_production",
+        U16,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_arr(
+        "last_month_transported",
+        U16,
+        2,
+        SL_MIN_VERSION,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "last_month_transported",
+        U16,
+        INDUSTRY_NUM_OUTPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    var("counter", U16),
+    var("type", U8),
+    var("owner", U8),
+    var("random_colour", U8),
+    cond_var("last_prod_year", U8, SL_MIN_VERSION, 31),
+    cond_var("last_prod_year", I32, 31, SL_MAX_VERSION),
+    var("was_cargo_delivered", U8),
+    cond_var("ctlflags", U8, SLV_GS_INDUSTRY_CONTROL, SL_MAX_VERSION),
+    cond_var("founder", U8, 70, SL_MAX_VERSION),
+    cond_var("construction_date", I32, 70, SL_MAX_VERSION),
+    cond_var("construction_type", U8, 70, SL_MAX_VERSION),
+    cond_var(
+        "last_cargo_accepted_at",
+        I32,
+        70,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+    ),
+    cond_arr(
+        "last_cargo_accepted_at",
+        I32,
+        INDUSTRY_NUM_INPUTS,
+        SLV_EXTEND_INDUSTRY_CARGO_SLOTS,
+        SL_MAX_VERSION,
+    ),
+    cond_var("selected_layout", U8, 73, SL_MAX_VERSION),
+    cond_var(
+        "exclusive_supplier",
+        U8,
+        SLV_GS_INDUSTRY_CONTROL,
+        SL_MAX_VERSION,
+    ),
+    cond_var(
+        "exclusive_consumer",
+        U8,
+        SLV_GS_INDUSTRY_CONTROL,
+        SL_MAX_VERSION,
+    ),
+    cond_arr("storage", U32, 16, 76, 161),
+    cond_ref("psa", 161, SL_MAX_VERSION),
+    null(1, 82, 197),
+    cond_var("random", U16, 82, SL_MAX_VERSION),
+    cond_str("text", SLV_INDUSTRY_TEXT, SL_MAX_VERSION),
+    null(32, 2, 144),
+];
+
+// The NewGRF entity mappings of industries, industry tiles, houses, objects and airports
+static ENTITY_MAPPING_DESC: &[SchemaField] = &[
+    var("grfid", U32),
+    var("entity_id", U8),
+    var("substitute_id", U8),
+];
+
+pub(crate) fn read_industry(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, INDUSTRY_DESC)
+}
+
+pub(crate) fn read_entity_mapping(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, ENTITY_MAPPING_DESC)
+}
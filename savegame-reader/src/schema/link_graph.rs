@@ -0,0 +1,86 @@
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::error::Result;
+use crate::save_file::SLV_LINKGRAPH_TRAVEL_TIME;
+use crate::schema::{
+    cond_var, null, read_object, struct_list, var, ItemReader, SchemaField, SL_MAX_VERSION,
+    SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::{ParsedField, ParsedFieldContent, ParsedFieldData};
+
+const SLV_187: u16 = 187;
+const SLV_191: u16 = 191;
+
+const INVALID_NODE: u16 = u16::MAX;
+
+static LINK_GRAPH_DESC: &[SchemaField] = &[var("last_compression", I32)];
+
+static NODE_DESC: &[SchemaField] = &[
+    cond_var("xy", U32, SLV_191, SL_MAX_VERSION),
+    var("supply", U32),
+    var("demand", U32),
+    var("station", U16),
+    var("last_update", I32),
+];
+
+static EDGE_DESC: &[SchemaField] = &[
+    null(4, SL_MIN_VERSION, SLV_191),
+    var("capacity", U32),
+    var("usage", U32),
+    cond_var(
+        "travel_time_sum",
+        U64,
+        SLV_LINKGRAPH_TRAVEL_TIME,
+        SL_MAX_VERSION,
+    ),
+    var("last_unrestricted_update", I32),
+    cond_var("last_restricted_update", I32, SLV_187, SL_MAX_VERSION),
+    var("next_edge", U16),
+];
+
+fn next_edge(edge: &[ParsedField]) -> u16 {
+    match edge.iter().find(|field| field.key == "next_edge") {
+        Some(ParsedField {
+            data: ParsedFieldData::Scalar(ParsedFieldContent::U16(next)),
+            ..
+        }) => *next,
+        _ => INVALID_NODE,
+    }
+}
+
+pub(crate) fn read_link_graph(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    let mut link_graph = read_object(reader, LINK_GRAPH_DESC)?;
+    let num_nodes = reader.read_u16::<BigEndian>()?;
+    link_graph.extend(read_object(reader, &[var("cargo", U8)])?);
+
+    let mut nodes = vec![];
+    for from in 0..num_nodes {
+        let mut node = read_object(reader, NODE_DESC)?;
+        let mut edges = vec![];
+        if reader.is_before(SLV_191) {
+            // Old savegames store the full adjacency matrix. Keep only the edges that are part of
+            // the node's edge list, like newer savegames do.
+            let mut matrix = (0..num_nodes)
+                .map(|_| read_object(reader, EDGE_DESC).map(Some))
+                .collect::<Result<Vec<_>>>()?;
+            let mut to = from;
+            while let Some(edge) = matrix.get_mut(usize::from(to)).and_then(Option::take) {
+                to = next_edge(&edge);
+                edges.push(edge);
+            }
+        } else {
+            let mut to = from;
+            while to != INVALID_NODE {
+                let edge = read_object(reader, EDGE_DESC)?;
+                to = next_edge(&edge);
+                edges.push(edge);
+            }
+        }
+        node.push(struct_list("edges", edges));
+        nodes.push(node);
+    }
+    link_graph.push(struct_list("nodes", nodes));
+
+    Ok(link_graph)
+}
@@ -0,0 +1,29 @@
+use crate::error::Result;
+use crate::schema::{
+    cond_arr, cond_var, read_object, str, var, ItemReader, SchemaField, SL_MAX_VERSION,
+    SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+const GRF_CONFIG_MAX_PARAMS: usize = 0x80;
+
+static GRF_CONFIG_DESC: &[SchemaField] = &[
+    str("filename"),
+    var("ident.grfid", U32),
+    cond_arr("ident.md5sum", U8, 16, SL_MIN_VERSION, SL_MAX_VERSION),
+    cond_var("version", U32, 151, SL_MAX_VERSION),
+    cond_arr(
+        "param",
+        U32,
+        GRF_CONFIG_MAX_PARAMS,
+        SL_MIN_VERSION,
+        SL_MAX_VERSION,
+    ),
+    var("num_params", U8),
+    cond_var("palette", U8, 101, SL_MAX_VERSION),
+];
+
+pub(crate) fn read_grf_config(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, GRF_CONFIG_DESC)
+}
@@ -0,0 +1,54 @@
+use crate::error::Result;
+use crate::schema::{
+    cond_ref, cond_var, null, read_object, reference, str, var, ItemReader, SchemaField,
+    SL_MAX_VERSION, SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+static ORDER_DESC: &[SchemaField] = &[
+    var("type", U8),
+    var("flags", U8),
+    var("dest", U16),
+    reference("next"),
+    cond_var("refit_cargo", U8, 36, SL_MAX_VERSION),
+    null(1, 36, 182),
+    cond_var("wait_time", U16, 67, SL_MAX_VERSION),
+    cond_var("travel_time", U16, 67, SL_MAX_VERSION),
+    cond_var("max_speed", U16, 172, SL_MAX_VERSION),
+    null(10, 5, 36),
+];
+
+static ORDER_LIST_DESC: &[SchemaField] = &[reference("first")];
+
+static ORDER_BACKUP_DESC: &[SchemaField] = &[
+    var("user", U32),
+    var("tile", U32),
+    var("group", U16),
+    cond_var("service_interval", U32, SL_MIN_VERSION, 192),
+    cond_var("service_interval", U16, 192, SL_MAX_VERSION),
+    str("name"),
+    null(2, SL_MIN_VERSION, 192),
+    cond_ref("clone", 192, SL_MAX_VERSION),
+    var("cur_real_order_index", U8),
+    cond_var("cur_implicit_order_index", U8, 176, SL_MAX_VERSION),
+    cond_var("current_order_time", U32, 176, SL_MAX_VERSION),
+    cond_var("lateness_counter", I32, 176, SL_MAX_VERSION),
+    cond_var("timetable_start", I32, 176, SL_MAX_VERSION),
+    cond_var("vehicle_flags", U8, 176, 180),
+    cond_var("vehicle_flags", U16, 180, SL_MAX_VERSION),
+    reference("orders"),
+];
+
+// Orders from before SLV_5 were packed into a single word, which isn't decoded
+pub(crate) fn read_order(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, ORDER_DESC)
+}
+
+pub(crate) fn read_order_list(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, ORDER_LIST_DESC)
+}
+
+pub(crate) fn read_order_backup(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    read_object(reader, ORDER_BACKUP_DESC)
+}
@@ -0,0 +1,171 @@
+use byteorder::ReadBytesExt;
+
+use crate::error::Result;
+use crate::schema::{
+    cond_arr, cond_ref, cond_ref_list, cond_var, null, read_object, reference, str, struct_list,
+    take_count, var, ItemReader, SchemaField, SL_MAX_VERSION, SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::{StringId, I32, I64, I8, U16, U32, U64, U8};
+use crate::table_reader::{ParsedField, ParsedFieldContent, ParsedFieldData};
+
+const SLV_EXTEND_CARGOTYPES: u16 = 199;
+const SLV_MULTITILE_DOCKS: u16 = 216;
+
+const FACIL_WAYPOINT: u8 = 1 << 7;
+
+static BASE_STATION_DESC: &[SchemaField] = &[
+    var("xy", U32),
+    reference("town"),
+    var("string_id", StringId),
+    str("name"),
+    var("delete_ctr", U8),
+    var("owner", U8),
+    var("facilities", U8),
+    var("build_date", I32),
+    var("random_bits", U16),
+    var("waiting_triggers", U8),
+    var("num_specs", U8),
+];
+
+static STATION_DESC: &[SchemaField] = &[
+    var("train_station.tile", U32),
+    var("train_station.w", U8),
+    var("train_station.h", U8),
+    reference("bus_stops"),
+    reference("truck_stops"),
+    var("ship_station.tile", U32),
+    cond_var("ship_station.w", U8, SLV_MULTITILE_DOCKS, SL_MAX_VERSION),
+    cond_var("ship_station.h", U8, SLV_MULTITILE_DOCKS, SL_MAX_VERSION),
+    cond_var(
+        "docking_station.tile",
+        U32,
+        SLV_MULTITILE_DOCKS,
+        SL_MAX_VERSION,
+    ),
+    cond_var("docking_station.w", U8, SLV_MULTITILE_DOCKS, SL_MAX_VERSION),
+    cond_var("docking_station.h", U8, SLV_MULTITILE_DOCKS, SL_MAX_VERSION),
+    var("airport.tile", U32),
+    cond_var("airport.w", U8, 140, SL_MAX_VERSION),
+    cond_var("airport.h", U8, 140, SL_MAX_VERSION),
+    var("airport.type", U8),
+    cond_var("airport.layout", U8, 145, SL_MAX_VERSION),
+    var("airport.flags", U64),
+    cond_var("airport.rotation", U8, 145, SL_MAX_VERSION),
+    cond_arr("airport.storage", U32, 16, 145, 161),
+    cond_ref("airport.psa", 161, SL_MAX_VERSION),
+    var("indtype", U8),
+    var("time_since_load", U8),
+    var("time_since_unload", U8),
+    var("last_vehicle_type", U8),
+    var("had_vehicle_of_type", U8),
+    cond_ref_list("loading_vehicles", SL_MIN_VERSION, SL_MAX_VERSION),
+    cond_var("always_accepted", U32, 127, SLV_EXTEND_CARGOTYPES),
+    cond_var(
+        "always_accepted",
+        U64,
+        SLV_EXTEND_CARGOTYPES,
+        SL_MAX_VERSION,
+    ),
+];
+
+static WAYPOINT_DESC: &[SchemaField] = &[
+    var("town_cn", U16),
+    cond_var("train_station.tile", U32, 124, SL_MAX_VERSION),
+    cond_var("train_station.w", U8, 124, SL_MAX_VERSION),
+    cond_var("train_station.h", U8, 124, SL_MAX_VERSION),
+];
+
+static GOODS_DESC: &[SchemaField] = &[
+    cond_var("waiting_acceptance", U16, SL_MIN_VERSION, 68),
+    cond_var("status", U8, 68, SL_MAX_VERSION),
+    null(2, 51, 68),
+    var("time_since_pickup", U8),
+    var("rating", U8),
+    cond_var("cargo_source", U8, SL_MIN_VERSION, 7),
+    cond_var("cargo_source", U16, 7, 68),
+    cond_var("cargo_source_xy", U32, 44, 68),
+    cond_var("cargo_days", U8, SL_MIN_VERSION, 68),
+    var("last_speed", U8),
+    var("last_age", U8),
+    cond_var("cargo_feeder_share", U32, 14, 65),
+    cond_var("cargo_feeder_share", I64, 65, 68),
+    cond_var("amount_fract", U8, 150, SL_MAX_VERSION),
+    cond_ref_list("packets", 68, 183),
+    cond_var("num_dests", U32, 183, SL_MAX_VERSION),
+    cond_var("cargo.reserved_count", U32, 181, SL_MAX_VERSION),
+    cond_var("link_graph", U16, 183, SL_MAX_VERSION),
+    cond_var("node", U16, 183, SL_MAX_VERSION),
+    cond_var("num_flows", U32, 183, SL_MAX_VERSION),
+    cond_var("max_waiting_cargo", U32, 183, SL_MAX_VERSION),
+];
+
+static FLOW_DESC: &[SchemaField] = &[
+    var("source", U16),
+    var("via", U16),
+    var("share", U32),
+    cond_var("restricted", I8, 187, SL_MAX_VERSION),
+];
+
+static CARGO_LIST_DESC: &[SchemaField] = &[
+    var("first", U16),
+    cond_ref_list("second", SL_MIN_VERSION, SL_MAX_VERSION),
+];
+
+static SPECLIST_DESC: &[SchemaField] = &[
+    cond_var("grfid", U32, 27, SL_MAX_VERSION),
+    cond_var("localidx", U8, 27, SL_MAX_VERSION),
+];
+
+fn read_goods(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    let mut goods = read_object(reader, GOODS_DESC)?;
+    let num_flows = take_count(&mut goods, "num_flows");
+    let num_dests = take_count(&mut goods, "num_dests");
+
+    let flows = (0..num_flows)
+        .map(|_| read_object(reader, FLOW_DESC))
+        .collect::<Result<Vec<_>>>()?;
+    goods.push(struct_list("flow", flows));
+    let cargo = (0..num_dests)
+        .map(|_| read_object(reader, CARGO_LIST_DESC))
+        .collect::<Result<Vec<_>>>()?;
+    goods.push(struct_list("cargo", cargo));
+    Ok(goods)
+}
+
+pub(crate) fn read_station(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    let facilities = reader.read_u8()?;
+    let waypoint = facilities & FACIL_WAYPOINT != 0;
+
+    let mut base = read_object(reader, BASE_STATION_DESC)?;
+    let num_specs = take_count(&mut base, "num_specs");
+
+    let mut station = vec![struct_list("base", vec![base])];
+    if waypoint {
+        station.extend(read_object(reader, WAYPOINT_DESC)?);
+    } else {
+        station.extend(read_object(reader, STATION_DESC)?);
+        let num_cargo = if reader.is_before(SLV_EXTEND_CARGOTYPES) {
+            32
+        } else {
+            64
+        };
+        let goods = (0..num_cargo)
+            .map(|_| read_goods(reader))
+            .collect::<Result<Vec<_>>>()?;
+        station.push(struct_list("goods", goods));
+    }
+
+    let speclist = (0..num_specs)
+        .map(|_| read_object(reader, SPECLIST_DESC))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut fields = vec![
+        ParsedField {
+            key: String::from("facilities"),
+            data: ParsedFieldData::Scalar(ParsedFieldContent::U8(facilities)),
+        },
+        struct_list(if waypoint { "waypoint" } else { "normal" }, vec![station]),
+    ];
+    fields.push(struct_list("speclist", speclist));
+    Ok(fields)
+}
@@ -0,0 +1,118 @@
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::error::Result;
+use crate::schema::{
+    cond_arr, cond_ref_list, cond_str, cond_var, null, read_object, read_struct_list, var,
+    ItemReader, SchemaField, SL_MAX_VERSION,
+};
+use crate::table_reader::DataType::*;
+use crate::table_reader::ParsedField;
+
+const SLV_EXTEND_CARGOTYPES: u16 = 199;
+const SLV_REMOVE_TOWN_CARGO_CACHE: u16 = 219;
+
+const MAX_COMPANIES: usize = 15;
+const NUM_TE: usize = 6;
+const ACCEPTANCE_GRID: usize = 4;
+
+static TOWN_DESC: &[SchemaField] = &[
+    cond_var("xy", U16, 0, 6),
+    cond_var("xy", U32, 6, SL_MAX_VERSION),
+    null(2, 0, 3),
+    null(4, 3, 85),
+    null(2, 0, 92),
+    cond_var("townnamegrfid", U32, 66, SL_MAX_VERSION),
+    var("townnametype", U16),
+    var("townnameparts", U32),
+    cond_str("name", 84, SL_MAX_VERSION),
+    var("flags", U8),
+    cond_var("statues", U8, 0, 104),
+    cond_var("statues", U16, 104, SL_MAX_VERSION),
+    null(1, 0, 2),
+    cond_var("have_ratings", U8, 0, 104),
+    cond_var("have_ratings", U16, 104, SL_MAX_VERSION),
+    cond_arr("ratings", I16, 8, 0, 104),
+    cond_arr("ratings", I16, MAX_COMPANIES, 104, SL_MAX_VERSION),
+    cond_arr("unwanted", I8, 8, 4, 104),
+    cond_arr("unwanted", I8, MAX_COMPANIES, 104, SL_MAX_VERSION),
+    // Passenger and mail statistics from before they were stored per cargo
+    cond_arr("supplied_pass_mail", U16, 8, 0, 9),
+    cond_arr("supplied_pass_mail", U32, 8, 9, 165),
+    null(2, 0, 164),
+    cond_arr("received_food_water", U16, 4, 0, 165),
+    cond_arr("goal", U32, NUM_TE, 165, SL_MAX_VERSION),
+    cond_str("text", 168, SL_MAX_VERSION),
+    cond_var("time_until_rebuild", U8, 0, 54),
+    cond_var("grow_counter", U8, 0, 54),
+    cond_var("growth_rate", U8, 0, 54),
+    cond_var("time_until_rebuild", U16, 54, SL_MAX_VERSION),
+    cond_var("grow_counter", U16, 54, SL_MAX_VERSION),
+    cond_var("growth_rate", I16, 54, 165),
+    cond_var("growth_rate", U16, 165, SL_MAX_VERSION),
+    var("fund_buildings_months", U8),
+    var("road_build_months", U8),
+    cond_var("exclusivity", U8, 2, SL_MAX_VERSION),
+    cond_var("exclusive_counter", U8, 2, SL_MAX_VERSION),
+    cond_var("larger_town", I8, 56, SL_MAX_VERSION),
+    cond_var("layout", U8, 113, SL_MAX_VERSION),
+    cond_ref_list("psa_list", 161, SL_MAX_VERSION),
+    null(4, 166, SLV_EXTEND_CARGOTYPES),
+    null(8, SLV_EXTEND_CARGOTYPES, SLV_REMOVE_TOWN_CARGO_CACHE),
+    null(30, 2, SLV_REMOVE_TOWN_CARGO_CACHE),
+];
+
+static SUPPLIED_DESC: &[SchemaField] = &[
+    cond_var("old_max", U32, 165, SL_MAX_VERSION),
+    cond_var("new_max", U32, 165, SL_MAX_VERSION),
+    cond_var("old_act", U32, 165, SL_MAX_VERSION),
+    cond_var("new_act", U32, 165, SL_MAX_VERSION),
+];
+
+static RECEIVED_DESC: &[SchemaField] = &[
+    cond_var("old_max", U16, 165, SL_MAX_VERSION),
+    cond_var("new_max", U16, 165, SL_MAX_VERSION),
+    cond_var("old_act", U16, 165, SL_MAX_VERSION),
+    cond_var("new_act", U16, 165, SL_MAX_VERSION),
+];
+
+pub(crate) fn read_town(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    let mut town = read_object(reader, TOWN_DESC)?;
+
+    let num_cargo = if reader.is_before(SLV_EXTEND_CARGOTYPES) {
+        32
+    } else {
+        64
+    };
+    let (supplied, received) = if reader.is_before(165) {
+        (0, 0)
+    } else {
+        (num_cargo, NUM_TE)
+    };
+    town.push(read_struct_list(
+        reader,
+        "supplied",
+        SUPPLIED_DESC,
+        supplied,
+    )?);
+    town.push(read_struct_list(
+        reader,
+        "received",
+        RECEIVED_DESC,
+        received,
+    )?);
+
+    // The cached cargo acceptance of the town's area is recalculated on load, so skip it
+    if !reader.is_before(166) && reader.is_before(SLV_REMOVE_TOWN_CARGO_CACHE) {
+        reader.skip(4)?;
+        let width = usize::from(reader.read_u16::<BigEndian>()?);
+        let height = usize::from(reader.read_u16::<BigEndian>()?);
+        let cell_size = if reader.is_before(SLV_EXTEND_CARGOTYPES) {
+            4
+        } else {
+            8
+        };
+        reader.skip((width / ACCEPTANCE_GRID) * (height / ACCEPTANCE_GRID) * cell_size)?;
+    }
+
+    Ok(town)
+}
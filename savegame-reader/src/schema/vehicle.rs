@@ -0,0 +1,272 @@
+use byteorder::ReadBytesExt;
+
+use crate::error::{Result, SaveGameError};
+use crate::save_file::SaveFile;
+use crate::schema::{
+    cond_arr, cond_deque, cond_ref, cond_ref_list, cond_str, cond_var, null, read_object,
+    reference, struct_list, var, ItemReader, SchemaField, SL_MAX_VERSION, SL_MIN_VERSION,
+};
+use crate::table_reader::DataType::{StringId, I32, I64, U16, U32, U8};
+use crate::table_reader::{ParsedField, ParsedFieldContent, ParsedFieldData};
+
+const SLV_ROADVEH_PATH_CACHE: u16 = 211;
+const SLV_SHIP_PATH_CACHE: u16 = 203;
+const SLV_SHIP_ROTATION: u16 = 204;
+const SLV_VEH_MOTION_COUNTER: u16 = 288;
+
+const NUM_MOVE_TO_ACTION: usize = 4;
+
+static COMMON_VEH_DESC: &[SchemaField] = &[
+    var("subtype", U8),
+    reference("next"),
+    cond_var("name", StringId, SL_MIN_VERSION, 84),
+    cond_str("name", 84, SL_MAX_VERSION),
+    cond_var("unitnumber", U8, SL_MIN_VERSION, 8),
+    cond_var("unitnumber", U16, 8, SL_MAX_VERSION),
+    var("owner", U8),
+    cond_var("tile", U16, SL_MIN_VERSION, 6),
+    cond_var("tile", U32, 6, SL_MAX_VERSION),
+    cond_var("dest_tile", U16, SL_MIN_VERSION, 6),
+    cond_var("dest_tile", U32, 6, SL_MAX_VERSION),
+    cond_var("x_pos", U16, SL_MIN_VERSION, 6),
+    cond_var("x_pos", U32, 6, SL_MAX_VERSION),
+    cond_var("y_pos", U16, SL_MIN_VERSION, 6),
+    cond_var("y_pos", U32, 6, SL_MAX_VERSION),
+    cond_var("z_pos", U8, SL_MIN_VERSION, 164),
+    cond_var("z_pos", I32, 164, SL_MAX_VERSION),
+    var("direction", U8),
+    null(2, SL_MIN_VERSION, 58),
+    var("spritenum", U8),
+    null(5, SL_MIN_VERSION, 58),
+    var("engine_type", U16),
+    null(2, SL_MIN_VERSION, 152),
+    var("cur_speed", U16),
+    var("subspeed", U8),
+    var("acceleration", U8),
+    cond_var(
+        "motion_counter",
+        U32,
+        SLV_VEH_MOTION_COUNTER,
+        SL_MAX_VERSION,
+    ),
+    var("progress", U8),
+    var("vehstatus", U8),
+    cond_var("last_station_visited", U8, SL_MIN_VERSION, 5),
+    cond_var("last_station_visited", U16, 5, SL_MAX_VERSION),
+    cond_var("last_loading_station", U16, 182, SL_MAX_VERSION),
+    var("cargo_type", U8),
+    cond_var("cargo_subtype", U8, 35, SL_MAX_VERSION),
+    cond_var("cargo_days", U8, SL_MIN_VERSION, 68),
+    cond_var("cargo_source", U8, SL_MIN_VERSION, 7),
+    cond_var("cargo_source", U16, 7, 68),
+    cond_var("cargo_source_xy", U32, 44, 68),
+    var("cargo_cap", U16),
+    cond_var("refit_cap", U16, 182, SL_MAX_VERSION),
+    cond_var("cargo_count", U16, SL_MIN_VERSION, 68),
+    cond_ref_list("cargo.packets", 68, SL_MAX_VERSION),
+    cond_arr(
+        "cargo.action_counts",
+        U32,
+        NUM_MOVE_TO_ACTION,
+        181,
+        SL_MAX_VERSION,
+    ),
+    cond_var("cargo_age_counter", U16, 162, SL_MAX_VERSION),
+    var("day_counter", U8),
+    var("tick_counter", U8),
+    cond_var("running_ticks", U8, 88, SL_MAX_VERSION),
+    var("cur_implicit_order_index", U8),
+    cond_var("cur_real_order_index", U8, 158, SL_MAX_VERSION),
+    null(1, SL_MIN_VERSION, 105),
+    // Before version 5 the type and flags of the current order shared a byte
+    cond_var("current_order.type", U8, SL_MIN_VERSION, 5),
+    cond_var("current_order.dest", U8, SL_MIN_VERSION, 5),
+    cond_var("current_order.type", U8, 5, SL_MAX_VERSION),
+    cond_var("current_order.flags", U8, 5, SL_MAX_VERSION),
+    cond_var("current_order.dest", U16, 5, SL_MAX_VERSION),
+    cond_var("current_order.refit_cargo", U8, 36, SL_MAX_VERSION),
+    null(1, 36, 182),
+    cond_var("current_order.wait_time", U16, 67, SL_MAX_VERSION),
+    cond_var("current_order.travel_time", U16, 67, SL_MAX_VERSION),
+    cond_var("current_order.max_speed", U16, 174, SL_MAX_VERSION),
+    cond_var("timetable_start", I32, 129, SL_MAX_VERSION),
+    reference("orders"),
+    cond_var("age", U16, SL_MIN_VERSION, 31),
+    cond_var("age", I32, 31, SL_MAX_VERSION),
+    cond_var("max_age", U16, SL_MIN_VERSION, 31),
+    cond_var("max_age", I32, 31, SL_MAX_VERSION),
+    cond_var("date_of_last_service", U16, SL_MIN_VERSION, 31),
+    cond_var("date_of_last_service", I32, 31, SL_MAX_VERSION),
+    cond_var("service_interval", U16, SL_MIN_VERSION, 31),
+    cond_var("service_interval", U32, 31, 180),
+    cond_var("service_interval", U16, 180, SL_MAX_VERSION),
+    var("reliability", U16),
+    var("reliability_spd_dec", U16),
+    var("breakdown_ctr", U8),
+    var("breakdown_delay", U8),
+    var("breakdowns_since_last_service", U8),
+    var("breakdown_chance", U8),
+    cond_var("build_year", U8, SL_MIN_VERSION, 31),
+    cond_var("build_year", I32, 31, SL_MAX_VERSION),
+    var("load_unload_ticks", U16),
+    cond_var("cargo_paid_for", U16, 45, SL_MAX_VERSION),
+    cond_var("vehicle_flags", U8, 40, 180),
+    cond_var("vehicle_flags", U16, 180, SL_MAX_VERSION),
+    cond_var("profit_this_year", I32, SL_MIN_VERSION, 65),
+    cond_var("profit_this_year", I64, 65, SL_MAX_VERSION),
+    cond_var("profit_last_year", I32, SL_MIN_VERSION, 65),
+    cond_var("profit_last_year", I64, 65, SL_MAX_VERSION),
+    cond_var("cargo_feeder_share", I32, 51, 65),
+    cond_var("cargo_feeder_share", I64, 65, 68),
+    cond_var("cargo_loaded_at_xy", U32, 51, 68),
+    cond_var("value", I32, SL_MIN_VERSION, 65),
+    cond_var("value", I64, 65, SL_MAX_VERSION),
+    cond_var("random_bits", U8, 2, SL_MAX_VERSION),
+    cond_var("waiting_triggers", U8, 2, SL_MAX_VERSION),
+    cond_ref("next_shared", 2, SL_MAX_VERSION),
+    null(2, 2, 69),
+    null(4, 69, 101),
+    cond_var("group_id", U16, 60, SL_MAX_VERSION),
+    cond_var("current_order_time", U32, 67, SL_MAX_VERSION),
+    cond_var("lateness_counter", I32, 67, SL_MAX_VERSION),
+    null(10, 2, 144),
+];
+
+static TRAIN_DESC: &[SchemaField] = &[
+    var("crash_anim_pos", U16),
+    var("force_proceed", U8),
+    var("railtype", U8),
+    var("track", U8),
+    cond_var("flags", U8, 2, 100),
+    cond_var("flags", U16, 100, SL_MAX_VERSION),
+    null(2, 2, 60),
+    cond_var("wait_counter", U16, 136, SL_MAX_VERSION),
+    null(2, 2, 20),
+    cond_var("gv_flags", U16, 139, SL_MAX_VERSION),
+    null(11, 2, 144),
+];
+
+static ROADVEH_DESC: &[SchemaField] = &[
+    var("state", U8),
+    var("frame", U8),
+    var("blocked_ctr", U16),
+    var("overtaking", U8),
+    var("overtaking_ctr", U8),
+    var("crashed_ctr", U16),
+    var("reverse_ctr", U8),
+    cond_deque("path.td", U8, SLV_ROADVEH_PATH_CACHE, SL_MAX_VERSION),
+    cond_deque("path.tile", U32, SLV_ROADVEH_PATH_CACHE, SL_MAX_VERSION),
+    null(2, 6, 69),
+    cond_var("gv_flags", U16, 139, SL_MAX_VERSION),
+    null(4, 69, 131),
+    null(2, 6, 131),
+    null(16, 2, 144),
+];
+
+static SHIP_DESC: &[SchemaField] = &[
+    var("state", U8),
+    cond_deque("path", U8, SLV_SHIP_PATH_CACHE, SL_MAX_VERSION),
+    cond_var("rotation", U8, SLV_SHIP_ROTATION, SL_MAX_VERSION),
+    null(16, 2, 144),
+];
+
+static AIRCRAFT_DESC: &[SchemaField] = &[
+    var("crashed_counter", U16),
+    var("pos", U8),
+    cond_var("targetairport", U8, SL_MIN_VERSION, 5),
+    cond_var("targetairport", U16, 5, SL_MAX_VERSION),
+    var("state", U8),
+    cond_var("previous_pos", U8, 2, SL_MAX_VERSION),
+    cond_var("last_direction", U8, 2, SL_MAX_VERSION),
+    cond_var("number_consecutive_turns", U8, 2, SL_MAX_VERSION),
+    cond_var("turn_counter", U8, 136, SL_MAX_VERSION),
+    cond_var("flags", U8, 167, SL_MAX_VERSION),
+    null(13, 2, 144),
+];
+
+static EFFECT_DESC: &[SchemaField] = &[
+    var("subtype", U8),
+    cond_var("tile", U16, SL_MIN_VERSION, 6),
+    cond_var("tile", U32, 6, SL_MAX_VERSION),
+    cond_var("x_pos", U16, SL_MIN_VERSION, 6),
+    cond_var("x_pos", I32, 6, SL_MAX_VERSION),
+    cond_var("y_pos", U16, SL_MIN_VERSION, 6),
+    cond_var("y_pos", I32, 6, SL_MAX_VERSION),
+    cond_var("z_pos", U8, SL_MIN_VERSION, 164),
+    cond_var("z_pos", I32, 164, SL_MAX_VERSION),
+    var("sprite_cache.sprite_seq.seq[0].sprite", U16),
+    null(5, SL_MIN_VERSION, 59),
+    var("progress", U8),
+    var("vehstatus", U8),
+    var("animation_state", U16),
+    var("animation_substate", U8),
+    cond_var("spritenum", U8, 2, SL_MAX_VERSION),
+    null(15, 2, 144),
+];
+
+static DISASTER_DESC: &[SchemaField] = &[
+    reference("next"),
+    var("subtype", U8),
+    cond_var("tile", U16, SL_MIN_VERSION, 6),
+    cond_var("tile", U32, 6, SL_MAX_VERSION),
+    cond_var("dest_tile", U16, SL_MIN_VERSION, 6),
+    cond_var("dest_tile", U32, 6, SL_MAX_VERSION),
+    cond_var("x_pos", U16, SL_MIN_VERSION, 6),
+    cond_var("x_pos", I32, 6, SL_MAX_VERSION),
+    cond_var("y_pos", U16, SL_MIN_VERSION, 6),
+    cond_var("y_pos", I32, 6, SL_MAX_VERSION),
+    cond_var("z_pos", U8, SL_MIN_VERSION, 164),
+    cond_var("z_pos", I32, 164, SL_MAX_VERSION),
+    var("direction", U8),
+    null(5, SL_MIN_VERSION, 58),
+    var("owner", U8),
+    var("vehstatus", U8),
+    cond_var("current_order.dest", U8, SL_MIN_VERSION, 5),
+    cond_var("current_order.dest", U16, 5, SL_MAX_VERSION),
+    var("sprite_cache.sprite_seq.seq[0].sprite", U16),
+    cond_var("age", U16, SL_MIN_VERSION, 31),
+    cond_var("age", I32, 31, SL_MAX_VERSION),
+    var("tick_counter", U8),
+    cond_var("image_override", U16, SL_MIN_VERSION, 191),
+    cond_var("image_override", U32, 191, SL_MAX_VERSION),
+    cond_var("big_ufo_destroyer_target", U16, SL_MIN_VERSION, 191),
+    cond_var("big_ufo_destroyer_target", U32, 191, SL_MAX_VERSION),
+    cond_var("flags", U8, 194, SL_MAX_VERSION),
+    null(16, 2, 144),
+];
+
+pub(crate) fn read_vehicle(reader: &mut ItemReader) -> Result<Vec<ParsedField>> {
+    let offset = reader.position();
+    let vehicle_type = reader.read_u8()?;
+
+    let (key, vehicle) = match vehicle_type {
+        0..=3 => {
+            let (key, schema) = match vehicle_type {
+                0 => ("train", TRAIN_DESC),
+                1 => ("roadveh", ROADVEH_DESC),
+                2 => ("ship", SHIP_DESC),
+                _ => ("aircraft", AIRCRAFT_DESC),
+            };
+            let common = read_object(reader, COMMON_VEH_DESC)?;
+            let mut vehicle = vec![struct_list("common", vec![common])];
+            vehicle.extend(read_object(reader, schema)?);
+            (key, vehicle)
+        }
+        4 => ("effect", read_object(reader, EFFECT_DESC)?),
+        5 => ("disaster", read_object(reader, DISASTER_DESC)?),
+        _ => {
+            return Err(SaveGameError::Corrupt {
+                offset,
+                reason: format!("unknown vehicle type {}", vehicle_type),
+            })
+        }
+    };
+
+    Ok(vec![
+        ParsedField {
+            key: String::from("type"),
+            data: ParsedFieldData::Scalar(ParsedFieldContent::U8(vehicle_type)),
+        },
+        struct_list(key, vec![vehicle]),
+    ])
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::{has_bit, to_percent8};
+use crate::error::{Result, SaveGameError};
+use crate::names::{base_station, station_name};
+use crate::table_reader::{ParsedFieldContent, ParsedFieldData, TableItem};
+use crate::vehicle::VehicleType;
+
+// GoodsEntry::GoodsEntryStatus
+const GES_ACCEPTANCE: u8 = 0;
+const GES_RATING: u8 = 1;
+
+#[wasm_bindgen(typescript_custom_section)]
+const STATION_TS: &'static str = r#"
+export interface Facilities {
+  train: boolean;
+  truck_stop: boolean;
+  bus_stop: boolean;
+  airport: boolean;
+  dock: boolean;
+  waypoint: boolean;
+}
+
+export interface GoodsEntry {
+  cargo: number;
+  rating: number;
+  rating_percent: number;
+  accepted: boolean;
+  waiting: number;
+  last_speed: number;
+  last_age: number;
+}
+
+export interface Station {
+  index: number;
+  name: string;
+  xy: number;
+  x: number;
+  y: number;
+  town: number;
+  owner: number;
+  facilities: Facilities;
+  last_vehicle_type?: VehicleType;
+  goods: GoodsEntry[];
+}
+"#;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct Facilities {
+    pub train: bool,
+    pub truck_stop: bool,
+    pub bus_stop: bool,
+    pub airport: bool,
+    pub dock: bool,
+    pub waypoint: bool,
+}
+
+impl Facilities {
+    pub fn from_byte(byte: u8) -> Facilities {
+        let facility = |n| has_bit(usize::from(byte), n);
+        Facilities {
+            train: facility(0),
+            truck_stop: facility(1),
+            bus_stop: facility(2),
+            airport: facility(3),
+            dock: facility(4),
+            waypoint: facility(7),
+        }
+    }
+}
+
+// The cargo a station has seen, `rating` going from 0 to 255 like in OpenTTD and
+// `rating_percent` being the percentage the game shows for it
+#[derive(Debug, Clone, Serialize)]
+pub struct GoodsEntry {
+    pub cargo: u8,
+    pub rating: u8,
+    pub rating_percent: u8,
+    pub accepted: bool,
+    pub waiting: u32,
+    pub last_speed: u8,
+    pub last_age: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Station {
+    pub index: usize,
+    pub name: String,
+    pub xy: u32,
+    pub x: u32,
+    pub y: u32,
+    pub town: usize,
+    pub owner: u8,
+    pub facilities: Facilities,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_vehicle_type: Option<VehicleType>,
+    pub goods: Vec<GoodsEntry>,
+}
+
+// Parts of other chunks a station refers to
+pub struct StationContext<'a> {
+    pub map_width: u32,
+    pub towns: HashMap<usize, &'a TableItem>,
+    // The cargo count of each cargo packet
+    pub packets: HashMap<usize, u32>,
+}
+
+impl Station {
+    pub fn from_item(item: &TableItem, context: &StationContext) -> Result<Station> {
+        let base = base_station(item).ok_or(SaveGameError::InvalidField(String::from("base")))?;
+        let xy: u32 = base.field("xy")?;
+        let town = base
+            .reference("town")
+            .ok_or(SaveGameError::InvalidField(String::from("town")))?;
+
+        // Waypoints have no cargo handling of their own
+        let normal = item.child("normal");
+        let goods = normal
+            .map(|normal| {
+                normal
+                    .structs("goods")
+                    .filter_map(|goods| GoodsEntry::from_item(goods, &context.packets))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Station {
+            index: item.index,
+            name: station_name(item, &context.towns),
+            xy,
+            x: xy % context.map_width,
+            y: xy / context.map_width,
+            town,
+            owner: base.field("owner")?,
+            facilities: Facilities::from_byte(base.field("facilities")?),
+            last_vehicle_type: normal
+                .and_then(|normal| normal.integer("last_vehicle_type"))
+                .and_then(|byte| VehicleType::from_byte(byte as u8)),
+            goods,
+        })
+    }
+}
+
+impl GoodsEntry {
+    // Cargos that were never picked up or accepted at the station are left out
+    fn from_item(item: &TableItem, packets: &HashMap<usize, u32>) -> Option<GoodsEntry> {
+        let status = item.integer("status").unwrap_or_default() as u8;
+        let waiting = packet_refs(item)
+            .filter_map(|packet| packets.get(&packet))
+            .sum();
+        let accepted = has_bit(usize::from(status), GES_ACCEPTANCE);
+        if !has_bit(usize::from(status), GES_RATING) && !accepted && waiting == 0 {
+            return None;
+        }
+
+        let rating = item.field("rating").ok()?;
+        Some(GoodsEntry {
+            cargo: item.index as u8,
+            rating,
+            rating_percent: to_percent8(rating),
+            accepted,
+            waiting,
+            last_speed: item.field("last_speed").ok()?,
+            last_age: item.field("last_age").ok()?,
+        })
+    }
+}
+
+// The cargo packets waiting at a station, which were kept in a single list before they got
+// grouped by their next hop in SLV_183. References are stored as the pool index plus one.
+fn packet_refs(goods: &TableItem) -> impl Iterator<Item = usize> + '_ {
+    goods
+        .structs("cargo")
+        .flat_map(|next_hop| ref_list(next_hop.get("second")))
+        .chain(ref_list(goods.get("packets")))
+        .filter_map(|packet| match packet {
+            ParsedFieldContent::U32(packet) if *packet > 0 => Some(*packet as usize - 1),
+            _ => None,
+        })
+}
+
+fn ref_list(data: Option<&ParsedFieldData>) -> &[ParsedFieldContent] {
+    match data {
+        Some(ParsedFieldData::List(refs)) => refs,
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::names::town_name;
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_stations_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let stations = save_game.stations().unwrap();
+        assert_eq!(stations.len(), 422);
+        let waiting: u32 = stations
+            .iter()
+            .flat_map(|station| &station.goods)
+            .map(|goods| goods.waiting)
+            .sum();
+        assert!(waiting > 0);
+        assert!(stations
+            .iter()
+            .any(|station| station.goods.iter().any(|goods| goods.accepted)));
+        // None of the stations were renamed, so they are all named after their town
+        let towns = save_game.indexed_items("CITY");
+        assert!(stations.iter().all(|station| {
+            let town = town_name(towns[&station.town]);
+            station.name.starts_with(&town) || station.name.ends_with(&town)
+        }));
+    }
+
+    #[test]
+    fn test_stations_from_tables() {
+        let save_game = load("./test-big.sav");
+        let (width, height) = save_game.map_size().unwrap();
+        let stations = save_game.stations().unwrap();
+        assert!(stations
+            .iter()
+            .all(|station| station.x < width && station.y < height));
+        assert!(stations.iter().any(|station| station.facilities.waypoint));
+        assert!(stations
+            .iter()
+            .flat_map(|station| &station.goods)
+            .all(|goods| goods.rating_percent <= 100));
+    }
+}
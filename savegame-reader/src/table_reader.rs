@@ -1,20 +1,103 @@
 use byteorder::{BigEndian, ReadBytesExt};
 use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{Serialize, Serializer};
-use std::io;
 
 use crate::common::has_bit;
+use crate::error::SaveGameError;
 use crate::save_file::SaveFile;
 
+// `index` is the position of the item in its chunk (the pool index other chunks refer to) or
+// in the list it is nested in
 #[derive(Debug)]
-pub struct TableItem(pub Vec<ParsedField>);
+pub struct TableItem {
+    pub index: usize,
+    pub fields: Vec<ParsedField>,
+}
+
+impl TableItem {
+    pub fn get(&self, key: &str) -> Option<&ParsedFieldData> {
+        self.fields
+            .iter()
+            .find(|field| field.key == key)
+            .map(|field| &field.data)
+    }
+
+    pub fn scalar(&self, key: &str) -> Option<&ParsedFieldContent> {
+        match self.get(key)? {
+            ParsedFieldData::Scalar(content) => Some(content),
+            ParsedFieldData::List(_) => None,
+        }
+    }
+
+    pub fn integer(&self, key: &str) -> Option<i64> {
+        self.scalar(key)?.as_i64()
+    }
+
+    // An integer field that must be present and fit into `T`
+    pub fn field<T: TryFrom<i64>>(&self, key: &str) -> Result<T, SaveGameError> {
+        self.integer(key)
+            .and_then(|value| T::try_from(value).ok())
+            .ok_or_else(|| SaveGameError::InvalidField(String::from(key)))
+    }
+
+    // The pool index an object reference points to. References are stored as the index plus one,
+    // leaving zero for none.
+    pub fn reference(&self, key: &str) -> Option<usize> {
+        usize::try_from(self.integer(key)?).ok()?.checked_sub(1)
+    }
+
+    // The pool indices of a list of object references, leaving out the ones pointing to nothing
+    pub fn references<'a>(&'a self, key: &str) -> impl Iterator<Item = usize> + 'a {
+        self.integers(key)
+            .filter_map(|reference| usize::try_from(reference).ok()?.checked_sub(1))
+    }
+
+    // A string field, which tables store as a list holding the string unless it is empty
+    pub fn string(&self, key: &str) -> Option<&str> {
+        let content = match self.get(key)? {
+            ParsedFieldData::Scalar(content) => content,
+            ParsedFieldData::List(contents) => contents.first()?,
+        };
+        match content {
+            ParsedFieldContent::String(string) if !string.is_empty() => Some(string),
+            _ => None,
+        }
+    }
+
+    // The integers of a list, or a single integer
+    pub fn integers<'a>(&'a self, key: &str) -> impl Iterator<Item = i64> + 'a {
+        let contents = match self.get(key) {
+            Some(ParsedFieldData::Scalar(content)) => std::slice::from_ref(content),
+            Some(ParsedFieldData::List(contents)) => contents.as_slice(),
+            None => &[],
+        };
+        contents.iter().filter_map(ParsedFieldContent::as_i64)
+    }
+
+    // The items of a list of structs, or of a single struct
+    pub fn structs<'a>(&'a self, key: &str) -> impl Iterator<Item = &'a TableItem> {
+        let contents = match self.get(key) {
+            Some(ParsedFieldData::Scalar(content)) => std::slice::from_ref(content),
+            Some(ParsedFieldData::List(contents)) => contents.as_slice(),
+            None => &[],
+        };
+        contents.iter().filter_map(|content| match content {
+            ParsedFieldContent::Struct(item) => Some(item),
+            _ => None,
+        })
+    }
+
+    pub fn child(&self, key: &str) -> Option<&TableItem> {
+        self.structs(key).next()
+    }
+}
 
 impl Serialize for TableItem {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let TableItem(fields) = self;
+        let fields = &self.fields;
 
         let mut map = serializer.serialize_map(Some(fields.len()))?;
         for field in fields {
@@ -26,8 +109,8 @@ impl Serialize for TableItem {
 
 #[derive(Debug)]
 pub struct ParsedField {
-    key: String,
-    data: ParsedFieldData,
+    pub key: String,
+    pub data: ParsedFieldData,
 }
 
 #[derive(Debug)]
@@ -70,6 +153,23 @@ pub enum ParsedFieldContent {
     Struct(TableItem),
 }
 
+impl ParsedFieldContent {
+    pub fn as_i64(&self) -> Option<i64> {
+        Some(match self {
+            ParsedFieldContent::I8(value) => i64::from(*value),
+            ParsedFieldContent::U8(value) => i64::from(*value),
+            ParsedFieldContent::I16(value) => i64::from(*value),
+            ParsedFieldContent::U16(value) => i64::from(*value),
+            ParsedFieldContent::I32(value) => i64::from(*value),
+            ParsedFieldContent::U32(value) => i64::from(*value),
+            ParsedFieldContent::I64(value) => *value,
+            ParsedFieldContent::U64(value) => *value as i64,
+            ParsedFieldContent::StringId(value) => i64::from(*value),
+            _ => return None,
+        })
+    }
+}
+
 impl Serialize for ParsedFieldContent {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -92,21 +192,23 @@ impl Serialize for ParsedFieldContent {
     }
 }
 
-pub fn read_table_header(reader: &mut impl SaveFile) -> io::Result<Vec<Field>> {
+pub fn read_table_header(reader: &mut impl SaveFile) -> Result<Vec<Field>, SaveGameError> {
     let mut fields = vec![];
     loop {
-        let var_type = VarType::from_byte(reader.read_u8()?);
-        match var_type {
-            None => break,
-            Some(var_type) => {
-                let key = reader.read_string()?;
-                fields.push(Field {
-                    key,
-                    var_type,
-                    children: None,
-                });
-            }
+        let type_byte = reader.read_u8()?;
+        if type_byte == 0 {
+            break;
         }
+        let var_type = VarType::from_byte(type_byte).ok_or(SaveGameError::UnknownFieldType {
+            field_type: type_byte,
+            offset: reader.position() - 1,
+        })?;
+        let key = reader.read_string()?;
+        fields.push(Field {
+            key,
+            var_type,
+            children: None,
+        });
     }
     for field in fields.as_mut_slice() {
         if field.var_type.data_type() == DataType::Struct {
@@ -123,26 +225,30 @@ pub fn read_table_header(reader: &mut impl SaveFile) -> io::Result<Vec<Field>> {
     Ok(fields)
 }
 
-pub fn read_table(decoder: &mut impl SaveFile, fields: Vec<Field>) -> io::Result<Vec<TableItem>> {
-    let mut index = 0usize;
+pub fn read_table(
+    decoder: &mut impl SaveFile,
+    fields: Vec<Field>,
+) -> Result<Vec<TableItem>, SaveGameError> {
     let mut parsed_items: Vec<TableItem> = Vec::new();
+    let mut index = 0usize;
     loop {
-        let mut size = decoder.read_gamma()?;
+        let size = decoder.read_gamma()?;
         if size == 0 {
             break;
         }
-        size -= 1;
-
-        if size == 0 {
+        index += 1;
+        if size == 1 {
             continue;
         }
-        index += 1;
 
-        let parsed_fields: io::Result<Vec<ParsedField>> = fields
+        let parsed_fields: Result<Vec<ParsedField>, SaveGameError> = fields
             .iter()
             .map(|field| field.parse_from(decoder))
             .collect();
-        parsed_items.push(TableItem(parsed_fields?));
+        parsed_items.push(TableItem {
+            index: index - 1,
+            fields: parsed_fields?,
+        });
     }
     Ok(parsed_items)
 }
@@ -150,23 +256,24 @@ pub fn read_table(decoder: &mut impl SaveFile, fields: Vec<Field>) -> io::Result
 pub fn read_sparse_table(
     decoder: &mut impl SaveFile,
     fields: Vec<Field>,
-) -> io::Result<Vec<TableItem>> {
-    let mut index = 0usize;
+) -> Result<Vec<TableItem>, SaveGameError> {
     let mut parsed_items: Vec<TableItem> = Vec::new();
     loop {
-        let mut size = decoder.read_gamma()?;
+        let size = decoder.read_gamma()?;
         if size == 0 {
             break;
         }
-        size -= 1;
 
-        index = decoder.read_gamma()?;
+        let index = decoder.read_gamma()?;
 
-        let parsed_fields: io::Result<Vec<ParsedField>> = fields
+        let parsed_fields: Result<Vec<ParsedField>, SaveGameError> = fields
             .iter()
             .map(|field| field.parse_from(decoder))
             .collect();
-        parsed_items.push(TableItem(parsed_fields?));
+        parsed_items.push(TableItem {
+            index,
+            fields: parsed_fields?,
+        });
     }
     Ok(parsed_items)
 }
@@ -187,22 +294,29 @@ impl Field {
         }
     }
 
-    pub fn parse_from(&self, reader: &mut impl SaveFile) -> io::Result<ParsedField> {
+    pub fn parse_from(&self, reader: &mut impl SaveFile) -> Result<ParsedField, SaveGameError> {
         let data = match &self.var_type {
             VarType::List(data_type) => {
                 let length = reader.read_gamma()?;
                 let mut items = vec![];
-                for i in 0..length {
+                for index in 0..length {
                     let value = if let Some(children) = &self.children {
                         let mut parsed_children = vec![];
                         for child_field in children {
                             parsed_children.push(child_field.parse_from(reader)?);
                         }
-                        ParsedFieldContent::Struct(TableItem(parsed_children))
+                        ParsedFieldContent::Struct(TableItem {
+                            index,
+                            fields: parsed_children,
+                        })
                     } else if *data_type == DataType::String {
+                        let offset = reader.position();
                         let mut buf = vec![0; length];
                         reader.read_exact(&mut buf)?;
-                        ParsedFieldContent::String(String::from(std::str::from_utf8(&buf).unwrap()))
+                        ParsedFieldContent::String(
+                            String::from_utf8(buf)
+                                .map_err(|_| SaveGameError::InvalidUtf8 { offset })?,
+                        )
                     } else {
                         data_type.read_from(reader)?
                     };
@@ -220,7 +334,10 @@ impl Field {
                     for child_field in children {
                         parsed_children.push(child_field.parse_from(reader)?);
                     }
-                    ParsedFieldContent::Struct(TableItem(parsed_children))
+                    ParsedFieldContent::Struct(TableItem {
+                        index: 0,
+                        fields: parsed_children,
+                    })
                 } else {
                     data_type.read_from(reader)?
                 };
@@ -243,11 +360,7 @@ enum VarType {
 
 impl VarType {
     fn from_byte(byte: u8) -> Option<VarType> {
-        if byte == 0 {
-            return None;
-        }
-
-        let data_type = DataType::from_byte(byte & 0b0000_1111);
+        let data_type = DataType::from_byte(byte & 0b0000_1111)?;
         let has_length_field = has_bit(usize::from(byte), 4);
 
         if has_length_field {
@@ -259,14 +372,14 @@ impl VarType {
 
     fn data_type(&self) -> DataType {
         match self {
-            VarType::Scalar(data_type) => data_type.clone(),
-            VarType::List(data_type) => data_type.clone(),
+            VarType::Scalar(data_type) => *data_type,
+            VarType::List(data_type) => *data_type,
         }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum DataType {
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum DataType {
     FileEnd,
     I8,
     U8,
@@ -282,8 +395,8 @@ enum DataType {
 }
 
 impl DataType {
-    fn from_byte(byte: u8) -> DataType {
-        match byte {
+    fn from_byte(byte: u8) -> Option<DataType> {
+        Some(match byte {
             0 => DataType::FileEnd,
             1 => DataType::I8,
             2 => DataType::U8,
@@ -296,11 +409,14 @@ impl DataType {
             9 => DataType::StringId,
             10 => DataType::String,
             11 => DataType::Struct,
-            _ => panic!("tried to map {} to DataType", byte),
-        }
+            _ => return None,
+        })
     }
 
-    fn read_from(&self, reader: &mut impl SaveFile) -> io::Result<ParsedFieldContent> {
+    pub(crate) fn read_from(
+        &self,
+        reader: &mut impl SaveFile,
+    ) -> Result<ParsedFieldContent, SaveGameError> {
         Ok(match self {
             DataType::I8 => ParsedFieldContent::I8(reader.read_i8()?),
             DataType::U8 => ParsedFieldContent::U8(reader.read_u8()?),
@@ -311,7 +427,12 @@ impl DataType {
             DataType::I64 => ParsedFieldContent::I64(reader.read_i64::<BigEndian>()?),
             DataType::U64 => ParsedFieldContent::U64(reader.read_u64::<BigEndian>()?),
             DataType::StringId => ParsedFieldContent::StringId(reader.read_u16::<BigEndian>()?),
-            unknown => panic!("undefined read_from for {:?}", unknown),
+            unknown => {
+                return Err(SaveGameError::Corrupt {
+                    offset: reader.position(),
+                    reason: format!("cannot read {:?} as a plain value", unknown),
+                })
+            }
         })
     }
 }
@@ -0,0 +1,376 @@
+use serde::Serialize;
+
+use crate::common::has_bit;
+use crate::map::{MapTile, TileType};
+
+// Decodes the variant specific bits of a tile following the map layout OpenTTD documents in
+// docs/landscape.html. Older savegames are converted to this layout by OpenTTD after loading, so
+// tiles of very old savegames may not decode correctly.
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum Tile {
+    Clear {
+        ground: ClearGround,
+        density: u8,
+        snow: bool,
+    },
+    Railway {
+        rail_type: u8,
+        kind: RailTileKind,
+    },
+    Road {
+        road_type: u8,
+        tram_type: u8,
+        kind: RoadTileKind,
+    },
+    House {
+        town: u16,
+        house_type: u16,
+        completed: bool,
+    },
+    Trees {
+        tree_type: u8,
+        count: u8,
+        growth: u8,
+        ground: TreeGround,
+        density: u8,
+    },
+    Station {
+        station: u16,
+        station_type: StationType,
+        water_class: WaterClass,
+    },
+    Water {
+        water_class: WaterClass,
+        kind: WaterTileKind,
+    },
+    Void,
+    Industry {
+        industry: u16,
+        gfx: u16,
+        completed: bool,
+    },
+    TunnelBridge {
+        transport: TransportType,
+        direction: DiagDirection,
+        is_bridge: bool,
+    },
+    Object {
+        object: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum ClearGround {
+    Grass,
+    Rough,
+    Rocks,
+    Fields,
+    Snow,
+    Desert,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum RailTileKind {
+    Normal { track_bits: u8 },
+    Signals { track_bits: u8 },
+    Depot { direction: DiagDirection },
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum RoadTileKind {
+    Normal {
+        road_bits: u8,
+        tram_bits: u8,
+    },
+    Crossing {
+        rail_type: u8,
+        along_y: bool,
+        barred: bool,
+    },
+    Depot {
+        direction: DiagDirection,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum TreeGround {
+    Grass,
+    Rough,
+    SnowDesert,
+    Shore,
+    RoughSnow,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum StationType {
+    Rail,
+    Airport,
+    Truck,
+    Bus,
+    Oilrig,
+    Dock,
+    Buoy,
+    RailWaypoint,
+    RoadWaypoint,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum WaterClass {
+    Sea,
+    Canal,
+    River,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum WaterTileKind {
+    Clear,
+    Coast,
+    Lock,
+    Depot,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum TransportType {
+    Rail,
+    Road,
+    Water,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum DiagDirection {
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+}
+
+// Extracts `count` bits starting at bit `start`, like OpenTTD's GB()
+fn gb(value: impl Into<u32>, start: u8, count: u8) -> u8 {
+    ((value.into() >> start) & ((1 << count) - 1)) as u8
+}
+
+fn bit(value: impl Into<u32>, n: u8) -> bool {
+    has_bit(value.into() as usize, n)
+}
+
+impl DiagDirection {
+    pub(crate) fn from_bits(bits: u8) -> DiagDirection {
+        match bits & 0b11 {
+            0 => DiagDirection::NorthEast,
+            1 => DiagDirection::SouthEast,
+            2 => DiagDirection::SouthWest,
+            _ => DiagDirection::NorthWest,
+        }
+    }
+}
+
+impl WaterClass {
+    pub(crate) fn of(tile: &MapTile) -> WaterClass {
+        match gb(tile.m1, 5, 2) {
+            0 => WaterClass::Sea,
+            1 => WaterClass::Canal,
+            2 => WaterClass::River,
+            _ => WaterClass::Invalid,
+        }
+    }
+}
+
+impl From<&MapTile> for Tile {
+    fn from(tile: &MapTile) -> Tile {
+        match tile.tile_type {
+            TileType::Clear => Tile::Clear {
+                ground: match gb(tile.m5, 2, 3) {
+                    0 => ClearGround::Grass,
+                    1 => ClearGround::Rough,
+                    2 => ClearGround::Rocks,
+                    3 => ClearGround::Fields,
+                    4 => ClearGround::Snow,
+                    _ => ClearGround::Desert,
+                },
+                density: gb(tile.m5, 0, 2),
+                snow: bit(tile.m3, 4),
+            },
+            TileType::Railway => Tile::Railway {
+                rail_type: gb(tile.m8, 0, 6),
+                kind: match gb(tile.m5, 6, 2) {
+                    0 => RailTileKind::Normal {
+                        track_bits: gb(tile.m5, 0, 6),
+                    },
+                    1 => RailTileKind::Signals {
+                        track_bits: gb(tile.m5, 0, 6),
+                    },
+                    _ => RailTileKind::Depot {
+                        direction: DiagDirection::from_bits(tile.m5),
+                    },
+                },
+            },
+            TileType::Road => Tile::Road {
+                road_type: gb(tile.m4, 0, 6),
+                tram_type: gb(tile.m8, 6, 6),
+                kind: match gb(tile.m5, 6, 2) {
+                    0 => RoadTileKind::Normal {
+                        road_bits: gb(tile.m5, 0, 4),
+                        tram_bits: gb(tile.m3, 0, 4),
+                    },
+                    1 => RoadTileKind::Crossing {
+                        rail_type: gb(tile.m8, 0, 6),
+                        along_y: bit(tile.m5, 0),
+                        barred: bit(tile.m5, 5),
+                    },
+                    _ => RoadTileKind::Depot {
+                        direction: DiagDirection::from_bits(tile.m5),
+                    },
+                },
+            },
+            TileType::House => Tile::House {
+                town: tile.m2,
+                house_type: u16::from(tile.m4) | u16::from(gb(tile.m3, 6, 1)) << 8,
+                completed: bit(tile.m3, 7),
+            },
+            TileType::Trees => Tile::Trees {
+                tree_type: tile.m3,
+                count: gb(tile.m5, 6, 2) + 1,
+                growth: gb(tile.m5, 0, 3),
+                ground: match gb(tile.m2, 6, 3) {
+                    0 => TreeGround::Grass,
+                    1 => TreeGround::Rough,
+                    2 => TreeGround::SnowDesert,
+                    3 => TreeGround::Shore,
+                    _ => TreeGround::RoughSnow,
+                },
+                density: gb(tile.m2, 4, 2),
+            },
+            TileType::Station => Tile::Station {
+                station: tile.m2,
+                station_type: match gb(tile.m6, 3, 4) {
+                    0 => StationType::Rail,
+                    1 => StationType::Airport,
+                    2 => StationType::Truck,
+                    3 => StationType::Bus,
+                    4 => StationType::Oilrig,
+                    5 => StationType::Dock,
+                    6 => StationType::Buoy,
+                    7 => StationType::RailWaypoint,
+                    _ => StationType::RoadWaypoint,
+                },
+                water_class: WaterClass::of(tile),
+            },
+            TileType::Water => Tile::Water {
+                water_class: WaterClass::of(tile),
+                kind: match gb(tile.m5, 4, 4) {
+                    0 => WaterTileKind::Clear,
+                    1 => WaterTileKind::Coast,
+                    2 => WaterTileKind::Lock,
+                    _ => WaterTileKind::Depot,
+                },
+            },
+            TileType::Void => Tile::Void,
+            TileType::Industry => Tile::Industry {
+                industry: tile.m2,
+                gfx: u16::from(tile.m5) | u16::from(gb(tile.m6, 2, 1)) << 8,
+                completed: bit(tile.m1, 7),
+            },
+            TileType::TunnelBridge => Tile::TunnelBridge {
+                transport: match gb(tile.m5, 2, 2) {
+                    0 => TransportType::Rail,
+                    1 => TransportType::Road,
+                    _ => TransportType::Water,
+                },
+                direction: DiagDirection::from_bits(tile.m5),
+                is_bridge: bit(tile.m5, 7),
+            },
+            TileType::Object => Tile::Object {
+                object: u32::from(tile.m2) | u32::from(tile.m5) << 16,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::loader::load_file;
+    use crate::map::{MapTile, TileMap, TileType};
+    use crate::save_file::CompressedSaveFile;
+    use crate::tile::{DiagDirection, RailTileKind, StationType, Tile, WaterClass};
+
+    fn map_tile(tile_type: TileType) -> MapTile {
+        MapTile {
+            tile_type,
+            height: 0,
+            owner: 0,
+            type_bits: 0,
+            m1: 0,
+            m2: 0,
+            m3: 0,
+            m4: 0,
+            m5: 0,
+            m6: 0,
+            m7: 0,
+            m8: 0,
+        }
+    }
+
+    #[test]
+    fn test_decode_rail_depot() {
+        let tile = MapTile {
+            m5: 0b1100_0010,
+            m8: 3,
+            ..map_tile(TileType::Railway)
+        };
+        assert_eq!(
+            Tile::from(&tile),
+            Tile::Railway {
+                rail_type: 3,
+                kind: RailTileKind::Depot {
+                    direction: DiagDirection::SouthWest
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_station_tiles() {
+        let file = File::open("./test_v196.sav").unwrap();
+        let save_file = CompressedSaveFile::new(file).unwrap();
+        let version = save_file.version;
+        let mut chunks = load_file(save_file).unwrap();
+        let map = TileMap::from_chunks(&mut chunks, version).unwrap();
+        let stations = chunks.get("STNN").unwrap().items().unwrap();
+
+        let mut station_tiles = 0;
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                if let Some(Tile::Station { station, .. }) = map.decode_tile(x, y) {
+                    assert!(stations
+                        .iter()
+                        .any(|item| item.index == usize::from(station)));
+                    station_tiles += 1;
+                }
+            }
+        }
+        assert!(station_tiles > 0);
+    }
+
+    #[test]
+    fn test_decode_buoy() {
+        let tile = MapTile {
+            m1: 0b0010_0000,
+            m2: 42,
+            m6: 6 << 3,
+            ..map_tile(TileType::Station)
+        };
+        assert_eq!(
+            Tile::from(&tile),
+            Tile::Station {
+                station: 42,
+                station_type: StationType::Buoy,
+                water_class: WaterClass::Canal
+            }
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::has_bit;
+use crate::error::Result;
+use crate::map::TileMap;
+use crate::names::town_name;
+use crate::save_file::SaveVersion;
+use crate::table_reader::TableItem;
+use crate::tile::Tile;
+
+const SLV_TOWN_CUSTOM_GROWTH: u16 = 198;
+
+// Town::flags
+const TOWN_IS_GROWING: u8 = 0;
+const TOWN_CUSTOM_GROWTH: u8 = 3;
+// Before SLV_TOWN_CUSTOM_GROWTH a custom growth rate was flagged in the rate itself
+const TOWN_GROW_RATE_CUSTOM: u16 = 0x8000;
+
+// House types from this one on are defined by NewGRFs
+const NEW_HOUSE_OFFSET: usize = 110;
+
+// The population and number of tiles of the original house types, following OpenTTD's
+// _original_house_specs. Multi-tile houses continue in the following house types, which have no
+// tiles of their own.
+const ORIGINAL_HOUSES: [(u8, u8); NEW_HOUSE_OFFSET] = [
+    // Temperate, some of which are shared with sub-arctic
+    (187, 1),
+    (85, 1),
+    (40, 1),
+    (5, 1),
+    (220, 1),
+    (220, 1),
+    (30, 1),
+    (140, 2),
+    (0, 0),
+    (0, 1),
+    (0, 1),
+    (0, 1),
+    (0, 1),
+    (150, 1),
+    (95, 1),
+    (95, 1),
+    (250, 1),
+    (120, 1),
+    (120, 1),
+    (60, 1),
+    (65, 4),
+    (0, 0),
+    (0, 0),
+    (0, 0),
+    (15, 1),
+    (12, 1),
+    (13, 1),
+    (100, 1),
+    (170, 1),
+    (100, 1),
+    (180, 1),
+    (35, 1),
+    (65, 4),
+    (0, 0),
+    (0, 0),
+    (0, 0),
+    (140, 1),
+    (15, 1),
+    (15, 1),
+    (35, 1),
+    (180, 4),
+    (0, 0),
+    (0, 0),
+    (0, 0),
+    // Sub-arctic
+    (75, 1),
+    (80, 1),
+    (20, 1),
+    (15, 1),
+    (10, 1),
+    (20, 1),
+    (150, 1),
+    (150, 1),
+    (160, 1),
+    (160, 1),
+    (200, 1),
+    (200, 1),
+    (8, 1),
+    (8, 1),
+    (30, 1),
+    (30, 1),
+    (5, 1),
+    (5, 1),
+    (15, 1),
+    (15, 1),
+    (90, 1),
+    (90, 1),
+    (140, 2),
+    (0, 0),
+    (140, 2),
+    (0, 0),
+    (110, 1),
+    (110, 1),
+    (190, 1),
+    (190, 1),
+    (250, 2),
+    (0, 0),
+    (250, 2),
+    (0, 0),
+    // Sub-tropical and toyland
+    (130, 1),
+    (130, 1),
+    (20, 1),
+    (20, 1),
+    (5, 1),
+    (200, 1),
+    (95, 1),
+    (95, 1),
+    (140, 1),
+    (40, 1),
+    (40, 1),
+    (15, 1),
+    (15, 1),
+    (25, 1),
+    (25, 1),
+    (30, 1),
+    (30, 1),
+    (35, 1),
+    (35, 1),
+    (50, 1),
+    (50, 1),
+    (60, 1),
+    (60, 1),
+    (80, 1),
+    (80, 1),
+    (100, 1),
+    (100, 1),
+    (120, 1),
+    (120, 1),
+    (15, 1),
+    (15, 1),
+    (40, 1),
+];
+
+#[wasm_bindgen(typescript_custom_section)]
+const TOWN_TS: &'static str = r#"
+export interface CargoHistory {
+  last_month: number;
+  last_month_max: number;
+  this_month: number;
+  this_month_max: number;
+}
+
+export interface SuppliedCargo extends CargoHistory {
+  cargo: number;
+}
+
+export type TownEffect = "Passengers" | "Mail" | "Goods" | "Water" | "Food";
+
+export interface ReceivedCargo extends CargoHistory {
+  effect: TownEffect;
+  goal: number;
+}
+
+export interface CompanyRating {
+  company: number;
+  rating: number;
+}
+
+export interface Town {
+  index: number;
+  name: string;
+  xy: number;
+  x: number;
+  y: number;
+  city: boolean;
+  house_tiles: number;
+  houses?: number;
+  population?: number;
+  growing: boolean;
+  growth_rate: number;
+  custom_growth_rate: boolean;
+  ratings: CompanyRating[];
+  supplied: SuppliedCargo[];
+  received: ReceivedCargo[];
+}
+"#;
+
+// OpenTTD's TransportedCargoStat, which keeps the running month next to the finished one
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct CargoHistory {
+    pub last_month: u32,
+    pub last_month_max: u32,
+    pub this_month: u32,
+    pub this_month_max: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SuppliedCargo {
+    pub cargo: u8,
+    #[serde(flatten)]
+    pub history: CargoHistory,
+}
+
+// The effects a cargo can have on a town, which is what received cargo is counted by
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum TownEffect {
+    Passengers,
+    Mail,
+    Goods,
+    Water,
+    Food,
+}
+
+const TOWN_EFFECTS: [TownEffect; 5] = [
+    TownEffect::Passengers,
+    TownEffect::Mail,
+    TownEffect::Goods,
+    TownEffect::Water,
+    TownEffect::Food,
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceivedCargo {
+    pub effect: TownEffect,
+    // The amount needed each month for the town to grow, 0 if there is none
+    pub goal: u32,
+    #[serde(flatten)]
+    pub history: CargoHistory,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompanyRating {
+    pub company: u8,
+    pub rating: i16,
+}
+
+// Towns don't store their population, OpenTTD counts it from the houses' specs after loading.
+// `houses` and `population` are counted the same way, which is only possible while the town has
+// no houses of NewGRFs. `house_tiles` is the number of map tiles covered by the town's houses.
+#[derive(Debug, Clone, Serialize)]
+pub struct Town {
+    pub index: usize,
+    pub name: String,
+    pub xy: u32,
+    pub x: u32,
+    pub y: u32,
+    pub city: bool,
+    pub house_tiles: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub houses: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub population: Option<u32>,
+    pub growing: bool,
+    // Ticks between two attempts to grow
+    pub growth_rate: u16,
+    pub custom_growth_rate: bool,
+    pub ratings: Vec<CompanyRating>,
+    pub supplied: Vec<SuppliedCargo>,
+    pub received: Vec<ReceivedCargo>,
+}
+
+// Parts of other chunks a town refers to
+pub struct TownContext {
+    pub version: SaveVersion,
+    pub map_width: u32,
+    pub houses: HashMap<usize, TownHouses>,
+}
+
+// The houses of a town on the map
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TownHouses {
+    pub tiles: u32,
+    // Houses are counted by their north tile, like OpenTTD does
+    pub houses: u32,
+    // Only completed houses are inhabited
+    pub population: u32,
+    pub newgrf_tiles: u32,
+}
+
+impl Town {
+    pub fn from_item(item: &TableItem, context: &TownContext) -> Result<Town> {
+        let xy: u32 = item.field("xy")?;
+        let flags: u8 = item.field("flags")?;
+        let mut growth_rate: u16 = item.field("growth_rate")?;
+        let mut custom_growth_rate = has_bit(usize::from(flags), TOWN_CUSTOM_GROWTH);
+        if context.version.is_before(SLV_TOWN_CUSTOM_GROWTH) {
+            custom_growth_rate = growth_rate & TOWN_GROW_RATE_CUSTOM != 0;
+            growth_rate &= !TOWN_GROW_RATE_CUSTOM;
+        }
+
+        let have_ratings = item.integer("have_ratings").unwrap_or_default() as usize;
+        let ratings = item
+            .integers("ratings")
+            .enumerate()
+            .filter(|(company, _)| has_bit(have_ratings, *company as u8))
+            .map(|(company, rating)| CompanyRating {
+                company: company as u8,
+                rating: rating as i16,
+            })
+            .collect();
+
+        let goals = item.integers("goal").collect::<Vec<_>>();
+        // Received cargo is counted per town effect, leaving out the effect of cargos that
+        // have none
+        let received = item
+            .structs("received")
+            .skip(1)
+            .zip(TOWN_EFFECTS)
+            .map(|(received, effect)| ReceivedCargo {
+                effect,
+                goal: goals.get(received.index).copied().unwrap_or_default() as u32,
+                history: CargoHistory::from_item(received),
+            })
+            .collect();
+
+        let houses = context.houses.get(&item.index).copied().unwrap_or_default();
+        let original_houses = houses.newgrf_tiles == 0;
+
+        Ok(Town {
+            index: item.index,
+            name: town_name(item),
+            xy,
+            x: xy % context.map_width,
+            y: xy / context.map_width,
+            city: item.integer("larger_town").unwrap_or_default() != 0,
+            house_tiles: houses.tiles,
+            houses: original_houses.then_some(houses.houses),
+            population: original_houses.then_some(houses.population),
+            growing: has_bit(usize::from(flags), TOWN_IS_GROWING),
+            growth_rate,
+            custom_growth_rate,
+            ratings,
+            supplied: item
+                .structs("supplied")
+                .map(|supplied| SuppliedCargo {
+                    cargo: supplied.index as u8,
+                    history: CargoHistory::from_item(supplied),
+                })
+                .filter(|supplied| supplied.history.last_month_max > 0)
+                .collect(),
+            received,
+        })
+    }
+}
+
+impl TownHouses {
+    // Counts the houses of every town on the map, by town
+    pub fn count(map: &TileMap) -> HashMap<usize, TownHouses> {
+        let mut counts: HashMap<usize, TownHouses> = HashMap::new();
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let Some(Tile::House {
+                    town,
+                    house_type,
+                    completed,
+                }) = map.decode_tile(x, y)
+                else {
+                    continue;
+                };
+                let houses = counts.entry(usize::from(town)).or_default();
+                houses.tiles += 1;
+                match ORIGINAL_HOUSES.get(usize::from(house_type)) {
+                    Some((population, tiles)) => {
+                        if *tiles > 0 {
+                            houses.houses += 1;
+                        }
+                        if completed {
+                            houses.population += u32::from(*population);
+                        }
+                    }
+                    None => houses.newgrf_tiles += 1,
+                }
+            }
+        }
+        counts
+    }
+}
+
+impl CargoHistory {
+    fn from_item(item: &TableItem) -> CargoHistory {
+        let value = |key| item.integer(key).unwrap_or_default() as u32;
+        CargoHistory {
+            last_month: value("old_act"),
+            last_month_max: value("old_max"),
+            this_month: value("new_act"),
+            this_month_max: value("new_max"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::town::TownEffect;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_towns_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let towns = save_game.towns().unwrap();
+        assert_eq!(towns.len(), 432);
+        assert!(towns.iter().all(|town| town.house_tiles > 0));
+        assert!(towns.iter().all(|town| town.received.len() == 5));
+
+        // Without NewGRFs every town's houses are known, with each multi-tile house counted once
+        assert_eq!(towns[0].house_tiles, 447);
+        assert_eq!(towns[0].houses, Some(434));
+        assert_eq!(towns[0].population, Some(36_523));
+        let tiles = towns.iter().map(|town| town.house_tiles).sum::<u32>();
+        let houses = towns.iter().filter_map(|town| town.houses).sum::<u32>();
+        // The map has 190 houses of four tiles and 247 houses of two tiles
+        assert_eq!(tiles - houses, 190 * 3 + 247);
+        assert!(towns
+            .iter()
+            .all(|town| town.population.is_some_and(|population| population > 0)));
+        assert_eq!(towns[0].received[0].effect, TownEffect::Passengers);
+    }
+
+    #[test]
+    fn test_towns_from_tables() {
+        let save_game = load("./test-big.sav");
+        let towns = save_game.towns().unwrap();
+        // Towns supply passengers as long as they have houses
+        assert!(towns
+            .iter()
+            .filter(|town| town.house_tiles > 0)
+            .all(|town| town.supplied.iter().any(|supplied| supplied.cargo == 0)));
+        assert!(towns.iter().any(|town| !town.ratings.is_empty()));
+        // The towns are built of the houses of a NewGRF, which sets their population itself
+        assert!(towns
+            .iter()
+            .all(|town| town.houses.is_none() && town.population.is_none()));
+    }
+}
@@ -0,0 +1,2576 @@
+use serde::Serialize;
+
+// Towns store the generator of their name as a special string id, counting from this one
+pub(crate) const SPECSTR_TOWNNAME_START: i64 = 0x20C0;
+
+// OpenTTD's built-in town name generators, in the order of their string ids
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum TownNameStyle {
+    EnglishOriginal,
+    French,
+    German,
+    EnglishAdditional,
+    LatinAmerican,
+    Silly,
+    Swedish,
+    Dutch,
+    Finnish,
+    Polish,
+    Slovak,
+    Norwegian,
+    Hungarian,
+    Austrian,
+    Romanian,
+    Czech,
+    Swiss,
+    Danish,
+    Turkish,
+    Italian,
+    Catalan,
+}
+
+const TOWN_NAME_STYLES: [TownNameStyle; 21] = [
+    TownNameStyle::EnglishOriginal,
+    TownNameStyle::French,
+    TownNameStyle::German,
+    TownNameStyle::EnglishAdditional,
+    TownNameStyle::LatinAmerican,
+    TownNameStyle::Silly,
+    TownNameStyle::Swedish,
+    TownNameStyle::Dutch,
+    TownNameStyle::Finnish,
+    TownNameStyle::Polish,
+    TownNameStyle::Slovak,
+    TownNameStyle::Norwegian,
+    TownNameStyle::Hungarian,
+    TownNameStyle::Austrian,
+    TownNameStyle::Romanian,
+    TownNameStyle::Czech,
+    TownNameStyle::Swiss,
+    TownNameStyle::Danish,
+    TownNameStyle::Turkish,
+    TownNameStyle::Italian,
+    TownNameStyle::Catalan,
+];
+
+impl TownNameStyle {
+    pub fn from_string_id(string_id: i64) -> Option<TownNameStyle> {
+        let index = usize::try_from(string_id - SPECSTR_TOWNNAME_START).ok()?;
+        TOWN_NAME_STYLES.get(index).copied()
+    }
+}
+
+// Picks one of `max` entries from 16 bits of the seed, like OpenTTD's SeedChance()
+fn seed_chance(shift_by: u8, max: usize, seed: u32) -> usize {
+    (((seed >> shift_by) as usize & 0xFFFF) * max) >> 16
+}
+
+// Picks one of `max` entries by the remainder of the seed, like OpenTTD's SeedModChance()
+fn seed_mod_chance(shift_by: u8, max: usize, seed: u32) -> usize {
+    (seed >> shift_by) as usize % max
+}
+
+// Like seed_chance(), but picks nothing for the first `bias` outcomes
+fn seed_chance_bias(shift_by: u8, max: usize, seed: u32, bias: usize) -> Option<usize> {
+    seed_chance(shift_by, max + bias, seed).checked_sub(bias)
+}
+
+// Generates the name of a town from its `townnameparts` seed, like the built-in generators of
+// OpenTTD's townname.cpp
+pub fn generate_town_name(style: TownNameStyle, seed: u32) -> String {
+    match style {
+        TownNameStyle::EnglishOriginal => english_original(seed),
+        TownNameStyle::French => FRENCH_REAL[seed_chance(0, FRENCH_REAL.len(), seed)].to_string(),
+        TownNameStyle::German => german(seed),
+        TownNameStyle::EnglishAdditional => english_additional(seed),
+        TownNameStyle::LatinAmerican => {
+            SPANISH_REAL[seed_chance(0, SPANISH_REAL.len(), seed)].to_string()
+        }
+        TownNameStyle::Silly => silly(seed),
+        TownNameStyle::Swedish => swedish(seed),
+        TownNameStyle::Dutch => dutch(seed),
+        TownNameStyle::Finnish => finnish(seed),
+        TownNameStyle::Polish => polish(seed),
+        TownNameStyle::Slovak => SLOVAK_REAL[seed_chance(0, SLOVAK_REAL.len(), seed)].to_string(),
+        TownNameStyle::Norwegian => norwegian(seed),
+        TownNameStyle::Hungarian => hungarian(seed),
+        TownNameStyle::Austrian => austrian(seed),
+        TownNameStyle::Romanian => {
+            ROMANIAN_REAL[seed_chance(0, ROMANIAN_REAL.len(), seed)].to_string()
+        }
+        TownNameStyle::Czech => czech(seed),
+        TownNameStyle::Swiss => SWISS_REAL[seed_chance(0, SWISS_REAL.len(), seed)].to_string(),
+        TownNameStyle::Danish => danish(seed),
+        TownNameStyle::Turkish => turkish(seed),
+        TownNameStyle::Italian => italian(seed),
+        TownNameStyle::Catalan => catalan(seed),
+    }
+}
+
+const ENGLISH_ORIGINAL_1: &[&str] = &["Great ", "Little ", "New ", "Fort "];
+const ENGLISH_ORIGINAL_2: &[&str] = &[
+    "Wr", "B", "C", "Ch", "Br", "D", "Dr", "F", "Fr", "Fl", "G", "Gr", "H", "L", "M", "N", "P",
+    "Pr", "Pl", "R", "S", "S", "Sl", "T", "Tr", "W",
+];
+const ENGLISH_ORIGINAL_3: &[&str] = &["ar", "a", "e", "in", "on", "u", "un", "en"];
+const ENGLISH_ORIGINAL_4: &[&str] = &["n", "ning", "ding", "d", "", "t", "fing"];
+const ENGLISH_ORIGINAL_5: &[&str] = &[
+    "ville", "ham", "field", "ton", "town", "bridge", "bury", "wood", "ford", "hall", "ston",
+    "way", "stone", "borough", "ley", "head", "bourne", "pool", "worth", "hill", "well", "hattan",
+    "burg",
+];
+const ENGLISH_ORIGINAL_6: &[&str] = &[
+    "-on-sea", " Bay", " Market", " Cross", " Bridge", " Falls", " City", " Ridge", " Springs",
+];
+
+// Beginnings of generated English names that are replaced to avoid offensive or awkward words.
+// The original generator keeps "Fart" and replaces "Wrar" differently.
+const ENGLISH_REPLACEMENTS: &[(&str, &str)] = &[
+    ("Cunt", "East"),
+    ("Slag", "Pits"),
+    ("Slut", "Edin"),
+    ("Drar", "Quar"),
+    ("Dreh", "Bash"),
+    ("Frar", "Shor"),
+    ("Grar", "Aber"),
+    ("Brar", "Over"),
+];
+
+fn replace_english_words(name: &mut String, original: bool) {
+    for (word, replacement) in ENGLISH_REPLACEMENTS {
+        if name.starts_with(word) {
+            name.replace_range(..word.len(), replacement);
+        }
+    }
+    if !original && name.starts_with("Fart") {
+        name.replace_range(..4, "Boot");
+    }
+    if name.starts_with("Wrar") {
+        name.replace_range(..4, if original { "Inve" } else { "Stan" });
+    }
+}
+
+fn english_original(seed: u32) -> String {
+    let mut name = String::new();
+    if let Some(i) = seed_chance_bias(0, ENGLISH_ORIGINAL_1.len(), seed, 50) {
+        name += ENGLISH_ORIGINAL_1[i];
+    }
+    name += ENGLISH_ORIGINAL_2[seed_chance(4, ENGLISH_ORIGINAL_2.len(), seed)];
+    name += ENGLISH_ORIGINAL_3[seed_chance(7, ENGLISH_ORIGINAL_3.len(), seed)];
+    name += ENGLISH_ORIGINAL_4[seed_chance(10, ENGLISH_ORIGINAL_4.len(), seed)];
+    name += ENGLISH_ORIGINAL_5[seed_chance(13, ENGLISH_ORIGINAL_5.len(), seed)];
+    if let Some(i) = seed_chance_bias(15, ENGLISH_ORIGINAL_6.len(), seed, 60) {
+        name += ENGLISH_ORIGINAL_6[i];
+    }
+
+    // "Ce" and "Ci" are spelled with a K at the start of a name
+    if name.starts_with("Ce") || name.starts_with("Ci") {
+        name.replace_range(..1, "K");
+    }
+    replace_english_words(&mut name, true);
+    name
+}
+
+const ENGLISH_ADDITIONAL_PREFIX: &[&str] = &["Great ", "Little ", "New ", "Fort ", "St. ", "Old "];
+const ENGLISH_ADDITIONAL_1A: &[&str] = &[
+    "Pen", "Lough", "Stan", "Mill", "Kil", "Hal", "Char", "Nor", "Farn", "Har", "Bucks", "Chel",
+    "Amber", "Lap", "Hex", "Wim", "Tet", "Rom",
+];
+const ENGLISH_ADDITIONAL_1B1: &[&str] = &[
+    "Wr", "B", "C", "Ch", "Br", "D", "Dr", "F", "Fr", "Fl", "G", "Gr", "H", "L", "M", "N", "P",
+    "Pr", "Pl", "R", "S", "S", "Sl", "T", "Tr", "W",
+];
+const ENGLISH_ADDITIONAL_1B2: &[&str] = &[
+    "ar", "a", "e", "in", "on", "u", "o", "ee", "es", "ea", "un", "en",
+];
+const ENGLISH_ADDITIONAL_1B3A: &[&str] = &["n", "d", "", "t", "", ""];
+const ENGLISH_ADDITIONAL_1B3B: &[&str] = &["ning", "ding", "fing"];
+const ENGLISH_ADDITIONAL_2: &[&str] = &[
+    "ville", "ham", "field", "ton", "town", "borough", "bridge", "bury", "wood", "ditch", "ford",
+    "hall", "dean", "leigh", "dore", "ston", "stow", "church", "wich", "low", "way", "stone",
+    "minster", "ley", "head", "bourne", "pool", "worth", "hill", "well", "hattan", "burg", "berg",
+    "burgh", "port", "stoke", "haven", "stable", "stock", "side", "brook", "don", "den", "down",
+    "nor", "grove", "combe", "by", "say", "ney", "chester", "dale", "ness", "shaw", "thwaite",
+];
+const ENGLISH_ADDITIONAL_3: &[&str] = &[
+    "-on-sea", " Bay", " Market", " Beeches", " Common", " Park", " Heath", " Marsh", " Green",
+    " Castle", " End", " Rivers", " Cross", " Bridge", " Falls", " City", " Ridge", " Springs",
+];
+
+fn english_additional(seed: u32) -> String {
+    let mut name = String::new();
+    if let Some(i) = seed_chance_bias(0, ENGLISH_ADDITIONAL_PREFIX.len(), seed, 50) {
+        name += ENGLISH_ADDITIONAL_PREFIX[i];
+    }
+    if seed_chance(3, 20, seed) >= 14 {
+        name += ENGLISH_ADDITIONAL_1A[seed_chance(6, ENGLISH_ADDITIONAL_1A.len(), seed)];
+    } else {
+        name += ENGLISH_ADDITIONAL_1B1[seed_chance(6, ENGLISH_ADDITIONAL_1B1.len(), seed)];
+        name += ENGLISH_ADDITIONAL_1B2[seed_chance(9, ENGLISH_ADDITIONAL_1B2.len(), seed)];
+        if seed_chance(11, 20, seed) >= 4 {
+            name += ENGLISH_ADDITIONAL_1B3A[seed_chance(12, ENGLISH_ADDITIONAL_1B3A.len(), seed)];
+        } else {
+            name += ENGLISH_ADDITIONAL_1B3B[seed_chance(12, ENGLISH_ADDITIONAL_1B3B.len(), seed)];
+        }
+    }
+    name += ENGLISH_ADDITIONAL_2[seed_chance(14, ENGLISH_ADDITIONAL_2.len(), seed)];
+    if let Some(i) = seed_chance_bias(15, ENGLISH_ADDITIONAL_3.len(), seed, 60) {
+        name += ENGLISH_ADDITIONAL_3[i];
+    }
+    replace_english_words(&mut name, false);
+    name
+}
+
+const FRENCH_REAL: &[&str] = &[
+    "Agincourt",
+    "Lille",
+    "Dinan",
+    "Aubusson",
+    "Rodez",
+    "Bergerac",
+    "Bordeaux",
+    "Bayonne",
+    "Montpellier",
+    "Montelimar",
+    "Valence",
+    "Digne",
+    "Nice",
+    "Cannes",
+    "St. Tropez",
+    "Marseille",
+    "Narbonne",
+    "Sète",
+    "Aurillac",
+    "Guéret",
+    "Le Creusot",
+    "Nevers",
+    "Auxerre",
+    "Versailles",
+    "Meaux",
+    "Châlons",
+    "Compiègne",
+    "Metz",
+    "Chaumont",
+    "Langres",
+    "Bourg",
+    "Lyon",
+    "Vienne",
+    "Grenoble",
+    "Toulon",
+    "Rennes",
+    "Le Mans",
+    "Angers",
+    "Nantes",
+    "Châteauroux",
+    "Orléans",
+    "Lisieux",
+    "Cherbourg",
+    "Morlaix",
+    "Cognac",
+    "Agen",
+    "Tulle",
+    "Blois",
+    "Troyes",
+    "Charolles",
+    "Chambéry",
+    "Tours",
+    "St. Brieuc",
+    "St. Malo",
+    "La Rochelle",
+    "St. Flour",
+    "Le Puy",
+    "Vichy",
+    "St. Valery",
+    "Beaujolais",
+    "Albi",
+    "Paris",
+    "Biarritz",
+    "Béziers",
+    "Nîmes",
+    "Chamonix",
+    "Angoulême",
+    "Alençon",
+];
+
+const GERMAN_REAL: &[&str] = &[
+    "Berlin",
+    "Bonn",
+    "Bremen",
+    "Cottbus",
+    "Chemnitz",
+    "Dortmund",
+    "Dresden",
+    "Erfurt",
+    "Essen",
+    "Fulda",
+    "Gera",
+    "Kassel",
+    "Kiel",
+    "Köln",
+    "Lüneburg",
+    "Mainz",
+    "München",
+    "Münster",
+    "Potsdam",
+    "Stuttgart",
+    "Wiesbaden",
+];
+const GERMAN_PRE: &[&str] = &["Bad ", "Klein ", "Neu "];
+const GERMAN_1: &[&str] = &[
+    "Alb", "Als", "Ander", "Arns", "Bruns", "Bam", "Biele", "Cloppen", "Co", "Duis", "Düssel",
+    "Dannen", "Elb", "Els", "Elster", "Eichen", "Ems", "Fahr", "Falken", "Flens", "Frank", "Frei",
+    "Freuden", "Fried", "Fürsten", "Hahn", "Ham", "Harz", "Heidel", "Hers", "Herz", "Holz",
+    "Hildes", "Inns", "Ilsen", "Ingols", "Kel", "Kies", "Korn", "Kor", "Kreuz", "Kulm", "Langen",
+    "Lim", "Lohr", "Lüne", "Mel", "Michels", "Mühl", "Naum", "Nest", "Nord", "Nort", "Nien",
+    "Nidda", "Nieder", "Nürn", "Ober", "Offen", "Osna", "Olden", "Ols", "Oranien", "Pader",
+    "Quedlin", "Quer", "Ravens", "Regens", "Rott", "Ros", "Rüssels", "Saal", "Saar", "Salz",
+    "Schöne", "Schwein", "Sonder", "Sonnen", "Stein", "Strals", "Straus", "Süd", "Ton", "Unter",
+    "Ur", "Vor", "Wald", "War", "Wert", "Wester", "Witten", "Wolfs", "Würz",
+];
+const GERMAN_2: &[&str] = &[
+    "bach", "berg", "brück", "brücken", "burg", "dorf", "feld", "furt", "hausen", "haven", "heim",
+    "horst", "mund", "münster", "stadt", "stedt",
+];
+const GERMAN_3_AN_DER: &str = " an der ";
+const GERMAN_3_AM: &str = " am ";
+const GERMAN_4_AN_DER: &[&str] = &["Oder", "Spree", "Donau", "Saale", "Elbe"];
+const GERMAN_4_AM: &[&str] = &["Main"];
+
+fn german(seed: u32) -> String {
+    let seed_derivative = seed_chance(7, 28, seed);
+    let mut name = String::new();
+    if seed_derivative == 12 || seed_derivative == 19 {
+        name += GERMAN_PRE[seed_chance(2, GERMAN_PRE.len(), seed)];
+    }
+
+    let i = seed_chance(3, GERMAN_REAL.len() + GERMAN_1.len(), seed);
+    if i < GERMAN_REAL.len() {
+        name += GERMAN_REAL[i];
+    } else {
+        name += GERMAN_1[i - GERMAN_REAL.len()];
+        name += GERMAN_2[seed_chance(5, GERMAN_2.len(), seed)];
+    }
+
+    if seed_derivative == 24 {
+        let i = seed_chance(9, GERMAN_4_AN_DER.len() + GERMAN_4_AM.len(), seed);
+        if i < GERMAN_4_AN_DER.len() {
+            name += GERMAN_3_AN_DER;
+            name += GERMAN_4_AN_DER[i];
+        } else {
+            name += GERMAN_3_AM;
+            name += GERMAN_4_AM[i - GERMAN_4_AN_DER.len()];
+        }
+    }
+    name
+}
+
+const SPANISH_REAL: &[&str] = &[
+    "Caracas",
+    "Maracay",
+    "Maracaibo",
+    "Valencia",
+    "El Dorado",
+    "Morrocoy",
+    "Cata",
+    "Cataito",
+    "Ciudad Bolívar",
+    "Barquisimeto",
+    "Mérida",
+    "Puerto Ordaz",
+    "Santa Elena",
+    "San Juan",
+    "San Luis",
+    "San Rafael",
+    "Santiago",
+    "Barcelona",
+    "Barinas",
+    "San Cristóbal",
+    "San Francisco",
+    "San Martín",
+    "Guayana",
+    "San Carlos",
+    "El Limón",
+    "Coro",
+    "Corocoro",
+    "Puerto Ayacucho",
+    "Elorza",
+    "Arismendi",
+    "Trujillo",
+    "Carupano",
+    "Anaco",
+    "Lima",
+    "Cuzco",
+    "Iquitos",
+    "Callao",
+    "Huacho",
+    "Camaná",
+    "Puerto Chala",
+    "Santa Cruz",
+    "Quito",
+    "Cuenca",
+    "Tulcán",
+    "Esmeraldas",
+    "Ibarra",
+    "San Lorenzo",
+    "Macas",
+    "Morona",
+    "Machala",
+    "Zamora",
+    "Latacunga",
+    "Tena",
+    "Cochabamba",
+    "Ascensión",
+    "Magdalena",
+    "Santa Ana",
+    "Manoa",
+    "Sucre",
+    "Oruro",
+    "Uyuni",
+    "Potosí",
+    "Tupiza",
+    "La Quiaca",
+    "Yacuiba",
+    "San Borja",
+    "Fuerte Olimpo",
+    "Fortín Esteros",
+    "Campo Grande",
+    "Bogotá",
+    "El Banco",
+    "Zaragoza",
+    "Neiva",
+    "Mariano",
+    "Cali",
+    "La Palma",
+    "Andoas",
+    "Barranca",
+    "Montevideo",
+    "Valdivia",
+    "Arica",
+    "Temuco",
+    "Tocopilla",
+    "Mendoza",
+    "Santa Rosa",
+];
+
+const SILLY_1: &[&str] = &[
+    "Binky", "Blubber", "Bumble", "Crinkle", "Crusty", "Dangle", "Dribble", "Flippety", "Google",
+    "Muffin", "Nosey", "Pinker", "Quack", "Rumble", "Sleepy", "Sliggles", "Snooze", "Teddy",
+    "Tinkle", "Twister", "Pinker", "Hippo", "Itchy", "Jelly", "Jingle", "Jolly", "Kipper", "Lazy",
+    "Frogs", "Mouse", "Quack", "Cheeky", "Lumpy", "Grumpy", "Mangle", "Fiddle", "Slugs", "Noodles",
+    "Poodle", "Shiver", "Rumble", "Pixie", "Puddle", "Riddle", "Rattle", "Rickety", "Waffle",
+    "Sagging", "Sausage", "Egg", "Sleepy", "Scatter", "Scramble", "Silly", "Simple", "Trickle",
+    "Slippery", "Slimey", "Slimy", "Slider", "Smelly", "Smiggles", "Snigger", "Sniggles", "Snowy",
+    "Sticky", "Stinky", "Tingle", "Toad", "Toffee", "Tootle", "Trollop", "Twiddle", "Waggle",
+    "Wiggle", "Wobble", "Wriggle",
+];
+const SILLY_2: &[&str] = &[
+    "ton", "bury", "bottom", "ville", "well", "weed", "worth", "wig", "wick", "wood", "pool",
+    "head", "burg", "gate", "bridge",
+];
+
+fn silly(seed: u32) -> String {
+    let mut name = String::from(SILLY_1[seed_chance(0, SILLY_1.len(), seed)]);
+    name += SILLY_2[seed_chance(16, SILLY_2.len(), seed)];
+    name
+}
+
+const SWEDISH_1: &[&str] = &["Gamla ", "Lilla ", "Nya ", "Stora "];
+const SWEDISH_2: &[&str] = &[
+    "Boll", "Bor", "Ed", "En", "Erik", "Es", "Fin", "Fisk", "Grön", "Hag", "Halm", "Karl", "Kram",
+    "Kung", "Land", "Lid", "Lin", "Mal", "Malm", "Marie", "Ner", "Norr", "Oskar", "Sand", "Skog",
+    "Stock", "Stor", "Ström", "Sund", "Söder", "Tall", "Tratt", "Troll", "Upp", "Var", "Väster",
+    "Ängel", "Öster",
+];
+const SWEDISH_2A: &[&str] = &[
+    "B", "Br", "D", "Dr", "Dv", "F", "Fj", "Fl", "Fr", "G", "Gl", "Gn", "Gr", "H", "J", "K", "Kl",
+    "Kn", "Kr", "Kv", "L", "M", "N", "P", "Pl", "Pr", "R", "S", "Sk", "Skr", "Sl", "Sn", "Sp",
+    "Spr", "St", "Str", "Sv", "T", "Tr", "Tv", "V", "Vr",
+];
+const SWEDISH_2B: &[&str] = &["a", "e", "i", "o", "u", "y", "å", "ä", "ö"];
+const SWEDISH_2C: &[&str] = &[
+    "ck", "d", "dd", "g", "gg", "l", "ld", "m", "n", "nd", "ng", "nn", "p", "pp", "r", "rd", "rk",
+    "rp", "rr", "rt", "s", "sk", "st", "t", "tt", "v",
+];
+const SWEDISH_3: &[&str] = &[
+    "arp", "berg", "boda", "borg", "bro", "bukten", "by", "byn", "fors", "hammar", "hamn", "holm",
+    "hus", "hättan", "kulle", "köping", "lund", "löv", "sala", "skrona", "slätt", "spång", "stad",
+    "sund", "svall", "svik", "såker", "udde", "valla", "viken", "älv", "ås",
+];
+
+fn swedish(seed: u32) -> String {
+    let mut name = String::new();
+    if let Some(i) = seed_chance_bias(0, SWEDISH_1.len(), seed, 50) {
+        name += SWEDISH_1[i];
+    }
+    if seed_chance(4, 5, seed) >= 3 {
+        name += SWEDISH_2[seed_chance(7, SWEDISH_2.len(), seed)];
+    } else {
+        name += SWEDISH_2A[seed_chance(7, SWEDISH_2A.len(), seed)];
+        name += SWEDISH_2B[seed_chance(10, SWEDISH_2B.len(), seed)];
+        name += SWEDISH_2C[seed_chance(13, SWEDISH_2C.len(), seed)];
+    }
+    name += SWEDISH_3[seed_chance(16, SWEDISH_3.len(), seed)];
+    name
+}
+
+const DUTCH_1: &[&str] = &[
+    "Nieuw ", "Oud ", "Groot ", "Zuid ", "Noord ", "Oost ", "West ", "Klein ",
+];
+const DUTCH_2: &[&str] = &[
+    "Hoog", "Laag", "Zuider", "Zuid", "Ooster", "Oost", "Wester", "West", "Hoofd", "Midden",
+    "Eind", "Amster", "Amstel", "Dord", "Rotter", "Haar", "Til", "Enk", "Dok", "Veen", "Leidsch",
+    "Lely", "En", "Kaats", "U", "Maas", "Mar", "Bla", "Al", "Alk", "Eer", "Drie", "Ter", "Groes",
+    "Goes", "Soest", "Coe", "Uit", "Zwaag", "Hellen", "Slie", "IJ", "Grubben", "Groen", "Lek",
+    "Ridder", "Schie", "Olde", "Roose", "Haar", "Til", "Loos", "Hil",
+];
+const DUTCH_3: &[&str] = &[
+    "Drog", "Nat", "Valk", "Bob", "Dedem", "Kollum", "Best", "Hoend", "Leeuw", "Graaf", "Uithuis",
+    "Purm", "Hard", "Hell", "Werk", "Spijk", "Vink", "Wams", "Heerhug", "Koning",
+];
+const DUTCH_4: &[&str] = &["e", "er", "el", "en", "o", "s"];
+const DUTCH_5: &[&str] = &[
+    "stad", "vorst", "dorp", "dam", "beek", "doorn", "zijl", "zijlen", "lo", "muiden", "meden",
+    "vliet", "nisse", "daal", "vorden", "vaart", "mond", "zaal", "water", "duinen", "heuvel",
+    "geest", "kerk", "meer", "maar", "hoorn", "rade", "wijk", "berg", "heim", "sum", "richt",
+    "burg", "recht", "drecht", "trecht", "tricht", "dricht", "lum", "rum", "halen", "oever",
+    "wolde", "veen", "hoven", "gast", "kum", "hage", "dijk", "zwaag", "pomp", "huizen", "bergen",
+    "schede", "mere", "end",
+];
+
+fn dutch(seed: u32) -> String {
+    let mut name = String::new();
+    if let Some(i) = seed_chance_bias(0, DUTCH_1.len(), seed, 50) {
+        name += DUTCH_1[i];
+    }
+    if seed_chance(6, 9, seed) > 4 {
+        name += DUTCH_2[seed_chance(9, DUTCH_2.len(), seed)];
+    } else {
+        name += DUTCH_3[seed_chance(9, DUTCH_3.len(), seed)];
+        name += DUTCH_4[seed_chance(12, DUTCH_4.len(), seed)];
+    }
+    name += DUTCH_5[seed_chance(15, DUTCH_5.len(), seed)];
+    name
+}
+
+const FINNISH_REAL: &[&str] = &[
+    "Aijala",
+    "Kisko",
+    "Espoo",
+    "Helsinki",
+    "Tapiola",
+    "Järvelä",
+    "Lahti",
+    "Kotka",
+    "Hamina",
+    "Loviisa",
+    "Kouvola",
+    "Tampere",
+    "Oulu",
+    "Salo",
+    "Malmi",
+    "Pelto",
+    "Koski",
+    "Iisalmi",
+    "Raisio",
+    "Taavetti",
+    "Joensuu",
+    "Imatra",
+    "Tapanila",
+    "Pasila",
+    "Turku",
+    "Kupittaa",
+    "Vaasa",
+    "Pori",
+    "Rauma",
+    "Kolari",
+    "Lieksa",
+];
+// Words that can also be turned into a name of their own with "la" or "lä"
+const FINNISH_1: &[&str] = &[
+    "Hiekka", "Haapa", "Mylly", "Sauna", "Uusi", "Vanha", "Kesä", "Kuusi", "Pelto", "Tuomi",
+    "Terva", "Olki", "Heinä", "Seinä", "Rautatie", "Mäki", "Kaivo", "Kivi", "Koivu", "Kallio",
+    "Kangas", "Lahti", "Lammi", "Marja", "Nummi", "Ranta", "Saari", "Salo", "Suo", "Talo", "Vesi",
+    "Järvi",
+];
+const FINNISH_2: &[&str] = &[
+    "Lappeen",
+    "Lohjan",
+    "Savon",
+    "Lapin",
+    "Pitäjän",
+    "Martin",
+    "Kuusan",
+    "Kemi",
+    "Keri",
+    "Hämeen",
+    "Kangas",
+];
+const FINNISH_3: &[&str] = &[
+    "harju", "linna", "järvi", "kallio", "mäki", "nummi", "joki", "kylä", "lampi", "lahti",
+    "metsä", "suo", "laakso", "niitty", "luoto", "hovi", "ranta", "koski", "salo",
+];
+
+fn finnish(seed: u32) -> String {
+    if seed_chance(0, 15, seed) >= 10 {
+        return FINNISH_REAL[seed_chance(2, FINNISH_REAL.len(), seed)].to_string();
+    }
+
+    if seed_chance(0, 15, seed) >= 5 {
+        let mut name = String::from(FINNISH_1[seed_chance(0, FINNISH_1.len(), seed)]);
+        if name.ends_with('i') {
+            name.pop();
+            name.push('e');
+        }
+        // Vowel harmony decides the form of the suffix
+        if name.contains(['a', 'o', 'u', 'A', 'O', 'U']) {
+            name += "la";
+        } else {
+            name += "lä";
+        }
+        return name;
+    }
+
+    let i = seed_chance(2, FINNISH_1.len() + FINNISH_2.len(), seed);
+    let mut name = String::from(if i >= FINNISH_1.len() {
+        FINNISH_2[i - FINNISH_1.len()]
+    } else {
+        FINNISH_1[i]
+    });
+    name += FINNISH_3[seed_chance(10, FINNISH_3.len(), seed)];
+    name
+}
+
+const POLISH_1_M: &[&str] = &[
+    "Wielki ", "Mały ", "Zły ", "Dobry ", "Nowy ", "Stary ", "Złoty ", "Zielony ", "Biały ",
+    "Modry ", "Dębowy ",
+];
+const POLISH_1_F: &[&str] = &[
+    "Wielka ", "Mała ", "Zła ", "Dobra ", "Nowa ", "Stara ", "Złota ", "Zielona ", "Biała ",
+    "Modra ", "Dębowa ",
+];
+const POLISH_1_N: &[&str] = &[
+    "Wielkie ", "Małe ", "Złe ", "Dobre ", "Nowe ", "Stare ", "Złote ", "Zielone ", "Białe ",
+    "Modre ", "Dębowe ",
+];
+const POLISH_2_O: &[&str] = &[
+    "Frombork",
+    "Gniezno",
+    "Olsztyn",
+    "Toruń",
+    "Bydgoszcz",
+    "Terespol",
+    "Kraków",
+    "Poznań",
+    "Wrocław",
+    "Katowice",
+    "Cieszyn",
+    "Bytom",
+    "Hel",
+    "Konin",
+    "Lublin",
+    "Malbork",
+    "Sopot",
+    "Sosnowiec",
+    "Gdańsk",
+    "Gdynia",
+    "Sieradz",
+    "Sandomierz",
+    "Szczyrk",
+    "Szczytno",
+    "Szczecin",
+    "Zakopane",
+    "Szklarska Poręba",
+    "Bochnia",
+    "Golub-Dobrzyń",
+    "Chojnice",
+    "Ostrowiec",
+    "Otwock",
+    "Wolsztyn",
+];
+const POLISH_2_M: &[&str] = &[
+    "Jarocin",
+    "Gogolin",
+    "Tomaszów",
+    "Piotrków",
+    "Lidzbark",
+    "Rypin",
+    "Radzymin",
+    "Wołomin",
+    "Pruszków",
+    "Olsztynek",
+    "Cisek",
+    "Krotoszyn",
+    "Stoczek",
+    "Lubin",
+    "Lubicz",
+    "Milicz",
+    "Targ",
+    "Ostrów",
+    "Ozimek",
+    "Puck",
+    "Rzepin",
+    "Siewierz",
+    "Stargard",
+    "Starogard",
+    "Turek",
+    "Tymbark",
+    "Strzepcz",
+    "Strzebielin",
+    "Sochaczew",
+    "Grębocin",
+    "Gniew",
+    "Lubliniec",
+    "Lubasz",
+    "Lutomiersk",
+    "Niemodlin",
+    "Przeworsk",
+    "Ursus",
+    "Tyczyn",
+    "Sztum",
+    "Szczebrzeszyn",
+    "Wolin",
+    "Wrzeszcz",
+    "Zgierz",
+    "Zieleniec",
+    "Drobin",
+    "Garwolin",
+];
+const POLISH_2_F: &[&str] = &[
+    "Szprotawa",
+    "Pogorzelica",
+    "Motława",
+    "Lubawa",
+    "Nidzica",
+    "Kruszwica",
+    "Bierawa",
+    "Brodnica",
+    "Chojna",
+    "Krzepica",
+    "Ruda",
+    "Rumia",
+    "Tuchola",
+    "Trzebinia",
+    "Ustka",
+    "Warszawa",
+    "Bobowa",
+    "Dukla",
+    "Krynica",
+    "Murowana",
+    "Niemcza",
+    "Zaspa",
+    "Zawoja",
+    "Wola",
+    "Limanowa",
+    "Rabka",
+    "Skawina",
+    "Pilawa",
+];
+const POLISH_2_N: &[&str] = &[
+    "Lipsko",
+    "Pilzno",
+    "Przodkowo",
+    "Strzelno",
+    "Leszno",
+    "Jaworzno",
+    "Grodzisko",
+    "Krosno",
+    "Kłodzko",
+    "Mielno",
+    "Międzyrzecze",
+    "Opole",
+];
+const POLISH_3_M: &[&str] = &[
+    " Wybudowanie",
+    " Świętokrzyski",
+    " Górski",
+    " Morski",
+    " Zdrój",
+    " Wody",
+    " Bajoro",
+    " Krajeński",
+    " Śląski",
+    " Mazowiecki",
+    " Pomorski",
+    " Wielki",
+    " Maly",
+    " Warmiński",
+    " Mazurski",
+    " Mniejszy",
+    " Większy",
+    " Górny",
+    " Dolny",
+    " Wielki",
+    " Stary",
+    " Nowy",
+    " Wielkopolski",
+    " Wzgórze",
+    " Mosty",
+    " Kujawski",
+    " Małopolski",
+    " Podlaski",
+    " Lesny",
+];
+const POLISH_3_F: &[&str] = &[
+    " Wybudowanie",
+    " Świętokrzyska",
+    " Górska",
+    " Morska",
+    " Zdrój",
+    " Woda",
+    " Bajoro",
+    " Krajeńska",
+    " Śląska",
+    " Mazowiecka",
+    " Pomorska",
+    " Wielka",
+    " Mała",
+    " Warmińska",
+    " Mazurska",
+    " Mniejsza",
+    " Większa",
+    " Górna",
+    " Dolna",
+    " Wielka",
+    " Stara",
+    " Nowa",
+    " Wielkopolska",
+    " Wzgórza",
+    " Mosty",
+    " Kujawska",
+    " Malopolska",
+    " Podlaska",
+    " Leśna",
+];
+const POLISH_3_N: &[&str] = &[
+    " Wybudowanie",
+    " Świetokrzyskie",
+    " Górskie",
+    " Morskie",
+    " Zdrój",
+    " Wody",
+    " Bajoro",
+    " Krajeńskie",
+    " Śląskie",
+    " Mazowieckie",
+    " Pomorskie",
+    " Wielkie",
+    " Małe",
+    " Warmińskie ",
+    " Mazurskie ",
+    " Mniejsze",
+    " Większe",
+    " Górne",
+    " Dolne",
+    " Wielkie",
+    " Stare",
+    " Nowe",
+    " Wielkopolskie",
+    " Wzgórze",
+    " Mosty",
+    " Kujawskie",
+    " Małopolskie",
+    " Podlaskie",
+    " Leśne",
+];
+
+fn polish(seed: u32) -> String {
+    let i = seed_chance(
+        0,
+        POLISH_2_O.len() + POLISH_2_M.len() + POLISH_2_F.len() + POLISH_2_N.len(),
+        seed,
+    );
+    let j = seed_chance(2, 20, seed);
+
+    if i < POLISH_2_O.len() {
+        return POLISH_2_O[seed_chance(3, POLISH_2_O.len(), seed)].to_string();
+    }
+
+    // Adjectives agree with the gender of the noun
+    let (first, second, third) = if i < POLISH_2_O.len() + POLISH_2_M.len() {
+        (POLISH_1_M, POLISH_2_M, POLISH_3_M)
+    } else if i < POLISH_2_O.len() + POLISH_2_M.len() + POLISH_2_F.len() {
+        (POLISH_1_F, POLISH_2_F, POLISH_3_F)
+    } else {
+        (POLISH_1_N, POLISH_2_N, POLISH_3_N)
+    };
+    let mut name = String::new();
+    if j < 4 {
+        name += first[seed_chance(5, first.len(), seed)];
+    }
+    name += second[seed_chance(7, second.len(), seed)];
+    if (4..16).contains(&j) {
+        name += third[seed_chance(10, third.len(), seed)];
+    }
+    name
+}
+
+const SLOVAK_REAL: &[&str] = &[
+    "Bratislava",
+    "Bánovce nad Bebravou",
+    "Banská Bystrica",
+    "Banská Štiavnica",
+    "Bardejov",
+    "Brezno",
+    "Brezová pod Bradlom",
+    "Bytča",
+    "Čadca",
+    "Čierna nad Tisou",
+    "Detva",
+    "Dolný Kubín",
+    "Dunajská Streda",
+    "Gabčíkovo",
+    "Galanta",
+    "Gbely",
+    "Gelnica",
+    "Handlová",
+    "Hlohovec",
+    "Holíč",
+    "Humenné",
+    "Hurbanovo",
+    "Kežmarok",
+    "Komárno",
+    "Košice",
+    "Kremnica",
+    "Krompachy",
+    "Kúty",
+    "Leopoldov",
+    "Levoča",
+    "Liptovský Mikuláš",
+    "Lučenec",
+    "Malacky",
+    "Martin",
+    "Medzilaborce",
+    "Michalovce",
+    "Modra",
+    "Myjava",
+    "Námestovo",
+    "Nitra",
+    "Nová Baňa",
+    "Nové Mesto nad Váhom",
+    "Nové Zámky",
+    "Partizánske",
+    "Pezinok",
+    "Piešťany",
+    "Poltár",
+    "Poprad",
+    "Považská Bystrica",
+    "Prievidza",
+    "Púchov",
+    "Revúca",
+    "Rimavská Sobota",
+    "Rožňava",
+    "Ružomberok",
+    "Sabinov",
+    "Šaľa",
+    "Senec",
+    "Senica",
+    "Sereď",
+    "Skalica",
+    "Sládkovičovo",
+    "Smolenice",
+    "Snina",
+    "Stará Ľubovňa",
+    "Stará Turá",
+    "Strážske",
+    "Stropkov",
+    "Stupava",
+    "Štúrovo",
+    "Šulekovo",
+    "Topoľčany",
+    "Trebišov",
+    "Trenčín",
+    "Trnava",
+    "Turčianske Teplice",
+    "Tvrdošín",
+    "Vráble",
+    "Vranov nad Topľou",
+    "Záhorská Bystrica",
+    "Ždiar",
+    "Žiar nad Hronom",
+    "Žilina",
+    "Zlaté Moravce",
+    "Zvolen",
+];
+
+const NORWEGIAN_1: &[&str] = &[
+    "Arna", "Aust", "Bjørk", "Bjørn", "Brand", "Bøver", "Drag", "Dal", "Eids", "Elve", "Elv",
+    "Fagre", "Fjell", "Fjord", "Fosse", "Frid", "Fure", "Gran", "Grim", "Gul", "Haug", "Helle",
+    "Hol", "Høy", "Kald", "Kirke", "Kongs", "Lang", "Lille", "Mo", "Mykle", "Nord", "Ny", "Opp",
+    "Rauf", "Rød", "Rosen", "Sand", "Sel", "Sjø", "Skog", "Sol", "Stav", "Stein", "Stor", "Strand",
+    "Sunn", "Sval", "Tor", "Trond", "Ulv", "Vall", "Vest", "Vik", "Ås", "Øst",
+];
+const NORWEGIAN_2: &[&str] = &[
+    "berg", "borg", "by", "bø", "dal", "eid", "fjell", "fjord", "foss", "grund", "hamn", "havn",
+    "heim", "helle", "holm", "land", "lia", "lund", "mo", "nes", "rud", "sand", "set", "sjø",
+    "skog", "stad", "strand", "sund", "vik", "våg", "øy",
+];
+const NORWEGIAN_REAL: &[&str] = &[
+    "Alta",
+    "Arendal",
+    "Askim",
+    "Bergen",
+    "Bodø",
+    "Brevik",
+    "Bryne",
+    "Brønnøysund",
+    "Drammen",
+    "Drøbak",
+    "Egersund",
+    "Elverum",
+    "Flekkefjord",
+    "Florø",
+    "Fredrikstad",
+    "Gjøvik",
+    "Grimstad",
+    "Halden",
+    "Hamar",
+    "Hammerfest",
+    "Harstad",
+    "Haugesund",
+    "Holmestrand",
+    "Horten",
+    "Jessheim",
+    "Kongsberg",
+    "Kongsvinger",
+    "Kristiansand",
+    "Kristiansund",
+    "Larvik",
+    "Lillehammer",
+    "Lillesand",
+    "Lillestrøm",
+    "Mandal",
+    "Mo i Rana",
+    "Moss",
+    "Namsos",
+    "Narvik",
+    "Notodden",
+    "Orkanger",
+    "Oslo",
+    "Porsgrunn",
+    "Risør",
+    "Sandefjord",
+    "Sandnes",
+    "Sarpsborg",
+    "Skien",
+    "Stavanger",
+    "Steinkjer",
+    "Stjørdal",
+    "Tønsberg",
+    "Tromsø",
+    "Trondheim",
+    "Vadsø",
+    "Vardø",
+    "Ålesund",
+    "Åndalsnes",
+];
+
+fn norwegian(seed: u32) -> String {
+    // A real name in 3 out of 16 cases
+    if seed & 0xF < 3 {
+        return NORWEGIAN_REAL[seed_chance(4, NORWEGIAN_REAL.len(), seed)].to_string();
+    }
+    let mut name = String::from(NORWEGIAN_1[seed_chance(4, NORWEGIAN_1.len(), seed)]);
+    name += NORWEGIAN_2[seed_chance(11, NORWEGIAN_2.len(), seed)];
+    name
+}
+
+const HUNGARIAN_1: &[&str] = &["Nagy-", "Kis-", "Felső-", "Alsó-", "Új-"];
+const HUNGARIAN_2: &[&str] = &[
+    "Bodrog", "Dráva", "Duna", "Hej", "Hor", "Ipoly", "Kapos", "Kis", "Körös", "Maros", "Mura",
+    "Nyír", "Rába", "Sajó", "Sió", "Szamos", "Tisza", "Zala", "Ba", "Be", "Bo", "Bu", "Cse", "Dé",
+    "Dö", "Ér", "Fe", "Gyo", "Ha", "He", "Ke", "Ki", "Ko", "Me", "Mi", "Ne", "Pá", "Pe", "Sá",
+    "Se", "Szé", "Ta", "Te", "Ti", "Va", "Ve", "Zi",
+];
+const HUNGARIAN_3: &[&str] = &[
+    "achát",
+    "alja",
+    "almás",
+    "alom",
+    "apáti",
+    "bánya",
+    "bogár",
+    "csány",
+    "csaba",
+    "dány",
+    "egyháza",
+    "falu",
+    "falva",
+    "föld",
+    "gyarmat",
+    "halom",
+    "háza",
+    "hegy",
+    "hely",
+    "kend",
+    "keszi",
+    "kút",
+    "lak",
+    "laka",
+    "lova",
+    "mező",
+    "mind",
+    "patak",
+    "rét",
+    "sár",
+    "szeg",
+    "szállás",
+    "szentmárton",
+    "szőlős",
+    "telek",
+    "tó",
+    "újfalu",
+    "vár",
+    "vásár",
+    "völgy",
+];
+const HUNGARIAN_4: &[&str] = &["szentgyörgy", "szentimre", "szentjános", "szentmihály"];
+const HUNGARIAN_REAL: &[&str] = &[
+    "Ajka",
+    "Aszód",
+    "Badacsony",
+    "Baja",
+    "Budapest",
+    "Cegléd",
+    "Debrecen",
+    "Dunaújváros",
+    "Eger",
+    "Esztergom",
+    "Gödöllő",
+    "Győr",
+    "Gyula",
+    "Hatvan",
+    "Hódmezővásárhely",
+    "Kaposvár",
+    "Kecskemét",
+    "Keszthely",
+    "Kiskunhalas",
+    "Komárom",
+    "Miskolc",
+    "Mohács",
+    "Nagykanizsa",
+    "Nyíregyháza",
+    "Paks",
+    "Pápa",
+    "Pécs",
+    "Salgótarján",
+    "Siófok",
+    "Sopron",
+    "Szeged",
+    "Székesfehérvár",
+    "Szekszárd",
+    "Szentendre",
+    "Szolnok",
+    "Szombathely",
+    "Tatabánya",
+    "Vác",
+    "Veszprém",
+    "Zalaegerszeg",
+];
+
+fn hungarian(seed: u32) -> String {
+    if seed & 0x7 == 0 {
+        return HUNGARIAN_REAL[seed_chance(3, HUNGARIAN_REAL.len(), seed)].to_string();
+    }
+
+    let mut name = String::new();
+    let i = seed_chance(3, HUNGARIAN_1.len() * 3, seed);
+    if i < HUNGARIAN_1.len() {
+        name += HUNGARIAN_1[i];
+    }
+    name += HUNGARIAN_2[seed_chance(3, HUNGARIAN_2.len(), seed)];
+    name += HUNGARIAN_3[seed_chance(6, HUNGARIAN_3.len(), seed)];
+    let i = seed_chance(10, HUNGARIAN_4.len() * 3, seed);
+    if i < HUNGARIAN_4.len() {
+        name += HUNGARIAN_4[i];
+    }
+    name
+}
+
+const AUSTRIAN_A1: &[&str] = &["Bad ", "Deutsch ", "Gross ", "Klein ", "Markt ", "Maria "];
+const AUSTRIAN_A2: &[&str] = &[
+    "Aus", "Alten", "Braun", "Vösl", "Mittern", "Nuss", "Neu", "Walters", "Breiten", "Eisen",
+    "Feld", "Mittern", "Gall", "Obern", "Grat", "Heiligen", "Hof", "Holla", "Stein", "Eber",
+    "Eggen", "Enzers", "Frauen", "Herren", "Hof", "Hütt", "Kaisers", "Königs", "Knittel", "Lang",
+    "Ober", "Ollers", "Pfaffen", "Potten", "Salz", "Schwarz", "Stocker", "Unter", "Utten", "Vösen",
+    "Vill", "Weissen",
+];
+const AUSTRIAN_A3: &[&str] = &[
+    "see", "bach", "dorf", "ach", "stein", "hofen", "au", "ach", "kirch", "kirchen", "kreuz",
+    "brunn", "siedl", "markt", "wang", "haag",
+];
+const AUSTRIAN_A4: &[&str] = &[
+    "Bruck",
+    "Brunn",
+    "Gams",
+    "Grein",
+    "Ried",
+    "Faak",
+    "Zell",
+    "Spital",
+    "Kirchberg",
+    "Saal",
+    "Taferl",
+    "Wald",
+];
+const AUSTRIAN_A5: &[&str] = &["St. ", "Sankt "];
+const AUSTRIAN_A6: &[&str] = &[
+    "Aegyd",
+    "Andrä",
+    "Georgen",
+    "Jakob",
+    "Johann",
+    "Leonhard",
+    "Marein",
+    "Lorenzen",
+    "Margarethen",
+    "Martin",
+    "Michael",
+    "Nikolai",
+    "Oswald",
+    "Peter",
+    "Pölten",
+    "Stefan",
+    "Stephan",
+    "Thomas",
+    "Veit",
+    "Wolfgang",
+];
+const AUSTRIAN_F1: &[&str] = &[" an der ", " ob der "];
+const AUSTRIAN_F2: &[&str] = &[
+    "Donau", "Steyr", "Lafnitz", "Leitha", "Thaya", "Gail", "Drau", "Salzach", "Ybbs", "Traisen",
+    "Enns", "Mur", "Ill",
+];
+const AUSTRIAN_B1: &[&str] = &[" am "];
+const AUSTRIAN_B2: &[&str] = &[
+    "Brenner",
+    "Dachstein",
+    "Gebirge",
+    "Grossglockner",
+    "Hausruck",
+    "Semmering",
+    "Wagram",
+    "Wechsel",
+    "Wilden Kaiser",
+    "Ziller",
+];
+
+fn austrian(seed: u32) -> String {
+    let mut name = String::new();
+    if let Some(i) = seed_chance_bias(0, AUSTRIAN_A1.len(), seed, 15) {
+        name += AUSTRIAN_A1[i];
+    }
+
+    // Saints' towns are more likely to lie by a river or mountain
+    let mut j = 0;
+    let i = seed_chance(4, 6, seed);
+    if i >= 4 {
+        name += AUSTRIAN_A2[seed_chance(7, AUSTRIAN_A2.len(), seed)];
+        name += AUSTRIAN_A3[seed_chance(13, AUSTRIAN_A3.len(), seed)];
+    } else if i >= 2 {
+        name += AUSTRIAN_A5[seed_chance(7, AUSTRIAN_A5.len(), seed)];
+        name += AUSTRIAN_A6[seed_chance(9, AUSTRIAN_A6.len(), seed)];
+        j = 1;
+    } else {
+        name += AUSTRIAN_A4[seed_chance(7, AUSTRIAN_A4.len(), seed)];
+    }
+
+    let i = seed_chance(1, 6, seed);
+    if i >= 4 - j {
+        name += AUSTRIAN_F1[seed_chance(4, AUSTRIAN_F1.len(), seed)];
+        name += AUSTRIAN_F2[seed_chance(5, AUSTRIAN_F2.len(), seed)];
+    } else if i >= 2 - j {
+        name += AUSTRIAN_B1[seed_chance(4, AUSTRIAN_B1.len(), seed)];
+        name += AUSTRIAN_B2[seed_chance(5, AUSTRIAN_B2.len(), seed)];
+    }
+    name
+}
+
+const ROMANIAN_REAL: &[&str] = &[
+    "Adjud",
+    "Alba Iulia",
+    "Alexandria",
+    "Babadag",
+    "Bacău",
+    "Baia Mare",
+    "Băile Herculane",
+    "Băilești",
+    "Bârlad",
+    "Bicaz",
+    "Bistrița",
+    "Blaj",
+    "Borsec",
+    "Botoșani",
+    "Brăila",
+    "Brașov",
+    "București",
+    "Buftea",
+    "Buzău",
+    "Călărași",
+    "Caransebeș",
+    "Cernavodă",
+    "Cluj-Napoca",
+    "Constanța",
+    "Covasna",
+    "Craiova",
+    "Dej",
+    "Deva",
+    "Dorohoi",
+    "Drobeta-Turnu Severin",
+    "Drăgășani",
+    "Făgăraș",
+    "Fălticeni",
+    "Fetești",
+    "Focșani",
+    "Galați",
+    "Gheorgheni",
+    "Giurgiu",
+    "Hârșova",
+    "Hunedoara",
+    "Huși",
+    "Iași",
+    "Isalnița",
+    "Lugoj",
+    "Mangalia",
+    "Medgidia",
+    "Miercurea Ciuc",
+    "Mioveni",
+    "Mizil",
+    "Moinești",
+    "Năvodari",
+    "Odorheiu Secuiesc",
+    "Oltenița",
+    "Onești",
+    "Oradea",
+    "Orșova",
+    "Petroșani",
+    "Piatra Neamț",
+    "Pitești",
+    "Ploiești",
+    "Râmnicu Vâlcea",
+    "Reghin",
+    "Roman",
+    "Roșiorii de Vede",
+    "Salonta",
+    "Satu Mare",
+    "Sfântu Gheorghe",
+    "Sibiu",
+    "Sighetu Marmației",
+    "Sighișoara",
+    "Slatina",
+    "Slobozia",
+    "Suceava",
+    "Sulina",
+    "Târgoviște",
+    "Târgu Jiu",
+    "Târgu Mureș",
+    "Târgu Neamț",
+    "Târnăveni",
+    "Tecuci",
+    "Timișoara",
+    "Tulcea",
+    "Turda",
+    "Turnu Măgurele",
+    "Urziceni",
+    "Vaslui",
+    "Vatra Dornei",
+    "Victoria",
+    "Videle",
+    "Zalău",
+];
+
+// Czech names are put together from an optional adjective, a noun and an optional suffix, with
+// the adjective and the noun's ending agreeing in gender and number
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum CzechGender {
+    SingularMasculine,
+    SingularFeminine,
+    SingularNeuter,
+    PluralMasculine,
+    PluralFeminine,
+    PluralNeuter,
+    // Stems that take any ending, or any but a neuter one
+    Free,
+    NonNeuter,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum CzechPattern {
+    // Adjectives ending in -í, like "jarní"
+    Jarni,
+    // Adjectives ending in -ý, like "mladý"
+    Mlady,
+}
+
+// Which stem lengths an ending sounds right with
+const CZA_SHORT: u8 = 1;
+const CZA_MIDDLE: u8 = 2;
+const CZA_LONG: u8 = 4;
+const CZA_ALL: u8 = CZA_SHORT | CZA_MIDDLE | CZA_LONG;
+
+// What the parts can be combined with
+const CZC_NONE: u8 = 0;
+const CZC_COLOR: u8 = 1;
+const CZC_POSTFIX: u8 = 2;
+const CZC_NOPOSTFIX: u8 = 4;
+const CZC_ANY: u8 = CZC_COLOR | CZC_POSTFIX | CZC_NOPOSTFIX;
+
+struct CzechAdjective {
+    pattern: CzechPattern,
+    choose: u8,
+    name: &'static str,
+}
+
+struct CzechSubstantive {
+    gender: CzechGender,
+    allow: u8,
+    choose: u8,
+    name: &'static str,
+}
+
+const fn adj(pattern: CzechPattern, choose: u8, name: &'static str) -> CzechAdjective {
+    CzechAdjective {
+        pattern,
+        choose,
+        name,
+    }
+}
+
+const fn subst(gender: CzechGender, allow: u8, choose: u8, name: &'static str) -> CzechSubstantive {
+    CzechSubstantive {
+        gender,
+        allow,
+        choose,
+        name,
+    }
+}
+
+// Adjective endings by the gender of the noun and the adjective's pattern
+const CZECH_PATMOD: [[&str; 2]; 6] = [
+    ["í", "ý"],
+    ["í", "á"],
+    ["í", "é"],
+    ["í", "é"],
+    ["í", "é"],
+    ["í", "á"],
+];
+
+const CZECH_REAL: &[&str] = &[
+    "Aš",
+    "Benešov",
+    "Beroun",
+    "Blansko",
+    "Břeclav",
+    "Brno",
+    "Bruntál",
+    "Česká Lípa",
+    "České Budějovice",
+    "Český Krumlov",
+    "Děčín",
+    "Domažlice",
+    "Dubí",
+    "Frýdek-Místek",
+    "Havlíčkův Brod",
+    "Hodonín",
+    "Hradec Králové",
+    "Humpolec",
+    "Cheb",
+    "Chomutov",
+    "Chrudim",
+    "Jablonec nad Nisou",
+    "Jeseník",
+    "Jičín",
+    "Jihlava",
+    "Jindřichův Hradec",
+    "Karlovy Vary",
+    "Karviná",
+    "Kladno",
+    "Klatovy",
+    "Kolín",
+    "Kosmonosy",
+    "Kroměříž",
+    "Kutná Hora",
+    "Liberec",
+    "Litoměřice",
+    "Louny",
+    "Manětín",
+    "Mělník",
+    "Mladá Boleslav",
+    "Most",
+    "Náchod",
+    "Nový Jičín",
+    "Nymburk",
+    "Olomouc",
+    "Opava",
+    "Orácov",
+    "Ostrava",
+    "Pardubice",
+    "Pelhřimov",
+    "Polžice",
+    "Písek",
+    "Plzeň",
+    "Praha",
+    "Prachatice",
+    "Přerov",
+    "Příbram",
+    "Prostějov",
+    "Rakovník",
+    "Rokycany",
+    "Rudná",
+    "Rychnov nad Kněžnou",
+    "Semily",
+    "Sokolov",
+    "Strakonice",
+    "Středokluky",
+    "Šumperk",
+    "Svitavy",
+    "Tábor",
+    "Tachov",
+    "Teplice",
+    "Třebíč",
+    "Trutnov",
+    "Uherské Hradiště",
+    "Ústí nad Labem",
+    "Ústí nad Orlicí",
+    "Vsetín",
+    "Vyškov",
+    "Žďár nad Sázavou",
+    "Zlín",
+    "Znojmo",
+];
+
+const CZECH_ADJ: &[CzechAdjective] = &[
+    adj(CzechPattern::Jarni, CZC_ANY, "Horní"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Dolní"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Přední"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Zadní"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Kostelní"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Havraní"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Říční"),
+    adj(CzechPattern::Jarni, CZC_ANY, "Jezerní"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Velký"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Malý"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Vysoký"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Český"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Moravský"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Slezský"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Německý"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Nový"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Starý"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Krásný"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Hezký"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Kamenný"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Dlouhý"),
+    adj(CzechPattern::Mlady, CZC_ANY, "Suchý"),
+    adj(CzechPattern::Mlady, CZC_COLOR, "Černý"),
+    adj(CzechPattern::Mlady, CZC_COLOR, "Bílý"),
+    adj(CzechPattern::Mlady, CZC_COLOR, "Červený"),
+    adj(CzechPattern::Mlady, CZC_COLOR, "Zelený"),
+    adj(CzechPattern::Mlady, CZC_COLOR, "Modrý"),
+];
+
+// Nouns used as they are
+const CZECH_SUBST_FULL: &[CzechSubstantive] = &[
+    subst(CzechGender::SingularMasculine, CZA_ALL, CZC_COLOR, "Sedlec"),
+    subst(CzechGender::SingularMasculine, CZA_ALL, CZC_COLOR, "Brod"),
+    subst(CzechGender::SingularMasculine, CZA_ALL, CZC_NONE, "Úval"),
+    subst(CzechGender::SingularMasculine, CZA_ALL, CZC_COLOR, "Žďár"),
+    subst(CzechGender::SingularMasculine, CZA_ALL, CZC_COLOR, "Smrk"),
+    subst(CzechGender::SingularFeminine, CZA_ALL, CZC_COLOR, "Hora"),
+    subst(CzechGender::SingularFeminine, CZA_ALL, CZC_COLOR, "Lhota"),
+    subst(CzechGender::SingularFeminine, CZA_ALL, CZC_COLOR, "Lípa"),
+    subst(CzechGender::SingularFeminine, CZA_ALL, CZC_COLOR, "Hůrka"),
+    subst(CzechGender::SingularNeuter, CZA_ALL, CZC_COLOR, "Pole"),
+    subst(CzechGender::SingularNeuter, CZA_ALL, CZC_COLOR, "Městečko"),
+    subst(CzechGender::PluralMasculine, CZA_ALL, CZC_COLOR, "Brody"),
+    subst(CzechGender::PluralFeminine, CZA_ALL, CZC_COLOR, "Lázně"),
+    subst(CzechGender::PluralFeminine, CZA_ALL, CZC_COLOR, "Chalupy"),
+    subst(CzechGender::PluralNeuter, CZA_ALL, CZC_COLOR, "Vrata"),
+];
+
+// Stems that get an ending, and possibly a postfix before it
+const CZECH_SUBST_STEM: &[CzechSubstantive] = &[
+    subst(
+        CzechGender::SingularMasculine,
+        CZA_MIDDLE,
+        CZC_COLOR,
+        "Kostel",
+    ),
+    subst(
+        CzechGender::SingularMasculine,
+        CZA_MIDDLE,
+        CZC_COLOR,
+        "Klášter",
+    ),
+    subst(CzechGender::SingularMasculine, CZA_SHORT, CZC_COLOR, "Lhot"),
+    subst(CzechGender::SingularFeminine, CZA_SHORT, CZC_COLOR, "Lhot"),
+    subst(CzechGender::SingularFeminine, CZA_SHORT, CZC_COLOR, "Hur"),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_NONE, "Sedl"),
+    subst(
+        CzechGender::Free,
+        CZA_SHORT | CZA_MIDDLE | CZA_LONG,
+        CZC_COLOR,
+        "Hrad",
+    ),
+    subst(CzechGender::NonNeuter, CZA_MIDDLE, CZC_NONE, "Pras"),
+    subst(CzechGender::NonNeuter, CZA_MIDDLE, CZC_NONE, "Baž"),
+    subst(CzechGender::NonNeuter, CZA_MIDDLE, CZC_NONE, "Tes"),
+    subst(CzechGender::NonNeuter, CZA_MIDDLE, CZC_NONE, "Uh"),
+    subst(
+        CzechGender::NonNeuter,
+        CZA_MIDDLE | CZA_LONG,
+        CZC_NONE,
+        "Bydl",
+    ),
+    subst(
+        CzechGender::NonNeuter,
+        CZA_MIDDLE | CZA_LONG,
+        CZC_NONE,
+        "Bydž",
+    ),
+    subst(
+        CzechGender::NonNeuter,
+        CZA_MIDDLE | CZA_LONG,
+        CZC_NONE,
+        "Biskup",
+    ),
+    subst(
+        CzechGender::NonNeuter,
+        CZA_MIDDLE | CZA_LONG,
+        CZC_NONE,
+        "Bořet",
+    ),
+    subst(
+        CzechGender::NonNeuter,
+        CZA_MIDDLE | CZA_LONG,
+        CZC_NONE,
+        "Prost",
+    ),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_COLOR, "Kamen"),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_COLOR, "Jasen"),
+    subst(
+        CzechGender::Free,
+        CZA_MIDDLE | CZA_LONG,
+        CZC_COLOR,
+        "Jablon",
+    ),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_COLOR, "Vrb"),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_COLOR, "Bor"),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_COLOR, "Lip"),
+    subst(CzechGender::Free, CZA_MIDDLE | CZA_LONG, CZC_COLOR, "Dub"),
+];
+
+const CZECH_SUBST_POSTFIX: &[&str] = &["av", "an", "at", "ov", "on", "ot", "ev", "en", "et"];
+
+// Endings grouped by gender, as a stem picks from the consecutive run of endings it agrees with
+const CZECH_SUBST_ENDING: &[CzechSubstantive] = &[
+    subst(
+        CzechGender::SingularMasculine,
+        CZA_SHORT | CZA_MIDDLE,
+        CZC_ANY,
+        "ec",
+    ),
+    subst(
+        CzechGender::SingularMasculine,
+        CZA_SHORT | CZA_MIDDLE,
+        CZC_ANY,
+        "ín",
+    ),
+    subst(CzechGender::SingularMasculine, CZA_ALL, CZC_ANY, "ov"),
+    subst(
+        CzechGender::SingularMasculine,
+        CZA_SHORT | CZA_LONG,
+        CZC_ANY,
+        "kov",
+    ),
+    subst(CzechGender::SingularMasculine, CZA_LONG, CZC_POSTFIX, "ín"),
+    subst(CzechGender::SingularMasculine, CZA_LONG, CZC_POSTFIX, "ník"),
+    subst(CzechGender::SingularFeminine, CZA_ALL, CZC_ANY, "ka"),
+    subst(CzechGender::SingularFeminine, CZA_MIDDLE, CZC_ANY, "inka"),
+    subst(CzechGender::SingularFeminine, CZA_ALL, CZC_ANY, "ná"),
+    subst(
+        CzechGender::SingularFeminine,
+        CZA_LONG,
+        CZC_COLOR | CZC_NOPOSTFIX,
+        "ava",
+    ),
+    subst(CzechGender::SingularNeuter, CZA_ALL, CZC_ANY, "ko"),
+    subst(CzechGender::SingularNeuter, CZA_ALL, CZC_ANY, "no"),
+    subst(
+        CzechGender::SingularNeuter,
+        CZA_SHORT | CZA_MIDDLE,
+        CZC_NOPOSTFIX,
+        "ovo",
+    ),
+    subst(CzechGender::PluralMasculine, CZA_ALL, CZC_ANY, "ky"),
+    subst(CzechGender::PluralMasculine, CZA_ALL, CZC_ANY, "any"),
+    subst(CzechGender::PluralFeminine, CZA_ALL, CZC_ANY, "ice"),
+    subst(CzechGender::PluralFeminine, CZA_ALL, CZC_ANY, "vice"),
+    subst(
+        CzechGender::PluralFeminine,
+        CZA_SHORT | CZA_MIDDLE,
+        CZC_NOPOSTFIX,
+        "ovice",
+    ),
+    subst(CzechGender::PluralNeuter, CZA_ALL, CZC_ANY, "a"),
+];
+
+const CZECH_SUFFIX: &[&str] = &[
+    "nad Cidlinou",
+    "nad Dyjí",
+    "nad Jihlavou",
+    "nad Labem",
+    "nad Lesy",
+    "nad Moravou",
+    "nad Nisou",
+    "nad Odrou",
+    "nad Ostravicí",
+    "nad Sázavou",
+    "nad Vltavou",
+    "pod Pradědem",
+    "pod Radhoštěm",
+    "pod Řípem",
+    "pod Sněžkou",
+    "pod Špičákem",
+    "pod Sedlem",
+    "v Čechách",
+    "na Moravě",
+];
+
+fn czech(seed: u32) -> String {
+    if seed_mod_chance(0, 4, seed) == 0 {
+        return CZECH_REAL[seed_mod_chance(4, CZECH_REAL.len(), seed)].to_string();
+    }
+
+    // Out of 32: 0..11 adjective, 12..13 adjective and suffix, 14..16 suffix, 17.. neither
+    let prob_tails = seed_mod_chance(2, 32, seed);
+    let mut do_prefix = prob_tails < 12;
+    let do_suffix = prob_tails > 11 && prob_tails < 17;
+
+    let prefix = if do_prefix {
+        seed_mod_chance(5, CZECH_ADJ.len() * 12, seed) / 12
+    } else {
+        0
+    };
+    let suffix = if do_suffix {
+        seed_mod_chance(7, CZECH_SUFFIX.len(), seed)
+    } else {
+        0
+    };
+
+    // A stem with an ending in three out of four cases
+    let mut stem = seed_mod_chance(9, CZECH_SUBST_FULL.len() + 3 * CZECH_SUBST_STEM.len(), seed);
+    let dynamic_subst = stem >= CZECH_SUBST_FULL.len();
+    let mut gender;
+    let mut choose;
+    let mut postfix = 0;
+    let mut ending = 0;
+    if !dynamic_subst {
+        gender = CZECH_SUBST_FULL[stem].gender;
+        choose = CZECH_SUBST_FULL[stem].choose;
+    } else {
+        stem = (stem - CZECH_SUBST_FULL.len()) % CZECH_SUBST_STEM.len();
+        gender = CZECH_SUBST_STEM[stem].gender;
+        choose = CZECH_SUBST_STEM[stem].choose;
+        let allow = CZECH_SUBST_STEM[stem].allow;
+
+        // A postfix in half of the cases, unless the stem insists
+        postfix = seed_mod_chance(14, CZECH_SUBST_POSTFIX.len() * 2, seed);
+        if choose & CZC_POSTFIX != 0 {
+            postfix %= CZECH_SUBST_POSTFIX.len();
+        }
+        if choose & CZC_NOPOSTFIX != 0 {
+            postfix += CZECH_SUBST_POSTFIX.len();
+        }
+        choose |= if postfix < CZECH_SUBST_POSTFIX.len() {
+            CZC_POSTFIX
+        } else {
+            CZC_NOPOSTFIX
+        };
+
+        let agrees = |ending: &CzechSubstantive| match gender {
+            CzechGender::Free => true,
+            CzechGender::NonNeuter => !matches!(
+                ending.gender,
+                CzechGender::SingularNeuter | CzechGender::PluralNeuter
+            ),
+            gender => gender == ending.gender,
+        };
+        let start = CZECH_SUBST_ENDING.iter().position(agrees).unwrap_or(0);
+        let candidates: Vec<usize> = (start..CZECH_SUBST_ENDING.len())
+            .take_while(|i| agrees(&CZECH_SUBST_ENDING[*i]))
+            .filter(|i| {
+                let ending = &CZECH_SUBST_ENDING[*i];
+                ending.choose & choose == choose && ending.allow & allow != 0
+            })
+            .collect();
+        ending = candidates[seed_mod_chance(16, candidates.len(), seed)];
+        gender = CZECH_SUBST_ENDING[ending].gender;
+    }
+
+    if do_prefix && CZECH_ADJ[prefix].choose & choose != choose {
+        do_prefix = false;
+    }
+
+    let mut name = String::new();
+    if do_prefix {
+        let adjective = &CZECH_ADJ[prefix];
+        let mut word = String::from(adjective.name);
+        word.pop();
+        name += &word;
+        name += CZECH_PATMOD[gender as usize][adjective.pattern as usize];
+        name.push(' ');
+    }
+
+    if dynamic_subst {
+        name += CZECH_SUBST_STEM[stem].name;
+        if let Some(post) = CZECH_SUBST_POSTFIX.get(postfix) {
+            let end = CZECH_SUBST_ENDING[ending].name;
+            let post_byte = |i| post.as_bytes().get(i).copied().unwrap_or(0);
+            let end_byte = |i| end.as_bytes().get(i).copied().unwrap_or(0);
+            // Avoids repetitions like "avava" and "anana"
+            if post.len() < 2
+                || post.len() > end.len()
+                || ((post_byte(1) != b'v' || post_byte(1) != end_byte(1))
+                    && post_byte(2) != end_byte(1))
+            {
+                name += post;
+                // k-i becomes c-i and h-i becomes z-i
+                if end.starts_with('i') {
+                    if name.ends_with('k') {
+                        name.pop();
+                        name.push('c');
+                    } else if name.ends_with('h') {
+                        name.pop();
+                        name.push('z');
+                    }
+                }
+            }
+        }
+        name += CZECH_SUBST_ENDING[ending].name;
+    } else {
+        name += CZECH_SUBST_FULL[stem].name;
+    }
+
+    if do_suffix {
+        name.push(' ');
+        name += CZECH_SUFFIX[suffix];
+    }
+    name
+}
+
+const SWISS_REAL: &[&str] = &[
+    "Aarau",
+    "Aesch",
+    "Altdorf",
+    "Arosa",
+    "Appenzell",
+    "Arbon",
+    "Altstätten",
+    "Baar",
+    "Baden",
+    "Bellinzona",
+    "Brig-Glis",
+    "Bienne",
+    "Bulle",
+    "Binningen",
+    "Burgdorf",
+    "Bern",
+    "Basel",
+    "Bülach",
+    "Carouge",
+    "Cham",
+    "Chiasso",
+    "Chur",
+    "Davos",
+    "Delsberg",
+    "Dübendorf",
+    "Emmen",
+    "Einsiedeln",
+    "Frauenfeld",
+    "Freiburg",
+    "Genf",
+    "Glarus",
+    "Gossau",
+    "Grenchen",
+    "Herisau",
+    "Horgen",
+    "Interlaken",
+    "Kloten",
+    "Kreuzlingen",
+    "Küsnacht",
+    "Langenthal",
+    "Lausanne",
+    "Liestal",
+    "Locarno",
+    "Lugano",
+    "Luzern",
+    "Martigny",
+    "Meilen",
+    "Monthey",
+    "Montreux",
+    "Morges",
+    "Muttenz",
+    "Neuchâtel",
+    "Neuhausen",
+    "Nyon",
+    "Olten",
+    "Pully",
+    "Rapperswil",
+    "Reinach",
+    "Renens",
+    "Riehen",
+    "Romanshorn",
+    "Schaffhausen",
+    "Schwyz",
+    "Siders",
+    "Sitten",
+    "Solothurn",
+    "Spiez",
+    "St. Gallen",
+    "St. Moritz",
+    "Thun",
+    "Uster",
+    "Uzwil",
+    "Vernier",
+    "Vevey",
+    "Wädenswil",
+    "Wettingen",
+    "Wetzikon",
+    "Wil",
+    "Winterthur",
+    "Wohlen",
+    "Yverdon",
+    "Zermatt",
+    "Zofingen",
+    "Zollikon",
+    "Zug",
+    "Zürich",
+];
+
+const DANISH_1: &[&str] = &[
+    "Gamle ", "Lille ", "Nye ", "Store ", "Kirke ", "Nørre ", "Vester ", "Sønder ", "Øster ",
+    "Hvide ", "Høje ", "Kongens ",
+];
+const DANISH_2: &[&str] = &[
+    "Ager",
+    "Alle",
+    "Aske",
+    "Balle",
+    "Bede",
+    "Birke",
+    "Bjerring",
+    "Bjæver",
+    "Blommehave",
+    "Blok",
+    "Bolder",
+    "Bred",
+    "Charlotten",
+    "Christians",
+    "Danne",
+    "Diana",
+    "Es",
+    "Fredens",
+    "Frederiks",
+    "Fugle",
+    "Fåre",
+    "Gille",
+    "Gis",
+    "Glo",
+    "Græ",
+    "Grinde",
+    "Gyde",
+    "Hade",
+    "Hals",
+    "Ha",
+    "Helle",
+    "Hjer",
+    "Hol",
+    "Hørs",
+    "Isen",
+    "Jægers",
+    "Karls",
+    "Kolde",
+    "Kongs",
+    "Krage",
+    "Lange",
+    "Lund",
+    "Marie",
+    "Mari",
+    "Næs",
+    "Ny",
+    "Odder",
+    "Ring",
+    "Ro",
+    "Rød",
+    "Sakse",
+    "Sands",
+    "Skov",
+    "Slage",
+    "Snede",
+    "Spøt",
+    "Sunds",
+    "Sæ",
+    "Tårn",
+    "Vejle",
+    "Ving",
+    "Vor",
+];
+const DANISH_3: &[&str] = &[
+    "by", "bjerg", "borg", "bro", "dal", "dam", "drup", "havn", "holm", "hus", "kilde", "køb",
+    "lev", "lund", "løse", "magle", "rup", "skov", "sted", "strup", "sund", "torp", "vig", "værk",
+];
+
+fn danish(seed: u32) -> String {
+    let mut name = String::new();
+    if let Some(i) = seed_chance_bias(0, DANISH_1.len(), seed, 50) {
+        name += DANISH_1[i];
+    }
+    name += DANISH_2[seed_chance(7, DANISH_2.len(), seed)];
+    name += DANISH_3[seed_chance(16, DANISH_3.len(), seed)];
+    name
+}
+
+const TURKISH_PREFIX: &[&str] = &[
+    "Akça", "Altın", "Bahçe", "Boz", "Büyük", "Çay", "Doğu", "Eski", "Güzel", "Kızıl", "Küçük",
+    "Orta", "Sarı", "Sultan", "Ulu", "Yeni",
+];
+const TURKISH_MIDDLE: &[&str] = &["agaç", "ayva", "çam", "elma", "kurt", "pazar", "yalı"];
+const TURKISH_SUFFIX: &[&str] = &[
+    "dere", "hisar", "kale", "kaya", "kent", "köy", "ören", "pınar", "saray", "su", "tepe", "yer",
+    "yurt",
+];
+const TURKISH_REAL: &[&str] = &[
+    "Adana",
+    "Adıyaman",
+    "Afyon",
+    "Ağrı",
+    "Amasya",
+    "Antalya",
+    "Artvin",
+    "Balıkesir",
+    "Bilecik",
+    "Bitlis",
+    "Bolu",
+    "Burdur",
+    "Bursa",
+    "Çanakkale",
+    "Çankırı",
+    "Denizli",
+    "Edirne",
+    "Elazığ",
+    "Erzurum",
+    "Eskişehir",
+    "Giresun",
+    "Gümüşhane",
+    "Hatay",
+    "Isparta",
+    "İçel",
+    "İstanbul",
+    "İzmir",
+    "Kars",
+    "Kastamonu",
+    "Kayseri",
+    "Kırklareli",
+    "Kocaeli",
+    "Konya",
+    "Kütahya",
+    "Malatya",
+    "Manisa",
+    "Kahramanmaraş",
+    "Mardin",
+    "Muğla",
+    "Muş",
+    "Nevşehir",
+    "Niğde",
+    "Rize",
+    "Sakarya",
+    "Samsun",
+    "Siirt",
+    "Sinop",
+    "Sivas",
+    "Trabzon",
+    "Şanlıurfa",
+    "Van",
+    "Yozgat",
+    "Zonguldak",
+    "Aksaray",
+    "Bayburt",
+    "Karaman",
+    "Şırnak",
+    "Bartın",
+    "Ardahan",
+    "Iğdır",
+    "Yalova",
+    "Karabük",
+    "Osmaniye",
+    "Düzce",
+];
+
+fn turkish(seed: u32) -> String {
+    let mut name = String::new();
+    match seed_mod_chance(0, 5, seed) {
+        0 => {
+            name += TURKISH_PREFIX[seed_mod_chance(2, TURKISH_PREFIX.len(), seed)];
+            name += TURKISH_MIDDLE[seed_mod_chance(4, TURKISH_MIDDLE.len(), seed)];
+            if seed_mod_chance(0, 7, seed) == 0 {
+                name += TURKISH_SUFFIX[seed_mod_chance(10, TURKISH_SUFFIX.len(), seed)];
+            }
+        }
+        1 | 2 => {
+            name += TURKISH_PREFIX[seed_mod_chance(2, TURKISH_PREFIX.len(), seed)];
+            name += TURKISH_SUFFIX[seed_mod_chance(4, TURKISH_SUFFIX.len(), seed)];
+        }
+        _ => name += TURKISH_REAL[seed_mod_chance(4, TURKISH_REAL.len(), seed)],
+    }
+    name
+}
+
+const ITALIAN_REAL: &[&str] = &[
+    "Roma",
+    "Milano",
+    "Napoli",
+    "Torino",
+    "Venezia",
+    "Firenze",
+    "Palermo",
+    "Genova",
+    "Parma",
+    "Bologna",
+    "Bari",
+    "Cagliari",
+    "Sassari",
+    "Pisa",
+    "Aosta",
+    "Brescia",
+    "Verona",
+    "Bolzano",
+    "Padova",
+    "Udine",
+    "Trieste",
+    "Livorno",
+    "Ancona",
+    "Perugia",
+    "Pescara",
+    "L'Aquila",
+    "Campobasso",
+    "Potenza",
+    "Cosenza",
+    "Reggio Calabria",
+    "Catania",
+    "Messina",
+    "Siracusa",
+    "Trento",
+    "Taranto",
+    "Benevento",
+    "Brindisi",
+    "Lecce",
+    "Modena",
+    "Ferrara",
+    "Ravenna",
+    "Rimini",
+    "Como",
+    "Lecco",
+    "Bergamo",
+    "Sondrio",
+    "Pavia",
+    "Cremona",
+    "Mantova",
+    "Piacenza",
+    "Alessandria",
+    "Asti",
+    "Cuneo",
+    "Novara",
+    "Vercelli",
+    "Biella",
+    "Savona",
+    "Imperia",
+    "La Spezia",
+    "Massa",
+    "Carrara",
+    "Lucca",
+    "Pistoia",
+    "Prato",
+    "Arezzo",
+    "Siena",
+    "Grosseto",
+    "Viterbo",
+    "Rieti",
+    "Latina",
+    "Frosinone",
+    "Terni",
+    "Foligno",
+    "Spoleto",
+    "Macerata",
+    "Ascoli",
+    "Teramo",
+    "Chieti",
+    "Isernia",
+    "Caserta",
+    "Avellino",
+    "Salerno",
+    "Foggia",
+    "Matera",
+    "Crotone",
+    "Catanzaro",
+    "Trapani",
+    "Agrigento",
+    "Caltanissetta",
+    "Enna",
+    "Ragusa",
+    "Nuoro",
+    "Oristano",
+];
+const ITALIAN_PREF: &[&str] = &[
+    "Alpe ",
+    "Borgo ",
+    "Cascina ",
+    "Castel ",
+    "Fonte ",
+    "Forte ",
+    "Malga ",
+    "Masseria ",
+    "Monte ",
+    "Petra ",
+    "Porto ",
+    "Rocca ",
+    "Villa ",
+];
+const ITALIAN_1M: &[&str] = &[
+    "Bel", "Borgo", "Bosco", "Campo", "Capo", "Casal", "Castel", "Colle", "Fiume", "Fonte", "Lago",
+    "Mezzo", "Monte", "Mon", "Orto", "Passo", "Prato", "Poggio", "Ponte", "Pozzo", "Sasso", "Tra",
+    "Tre", "Ver", "Vico",
+];
+const ITALIAN_1F: &[&str] = &[
+    "Acqua", "Bra", "Cala", "Casa", "Chiesa", "Citta", "Civita", "Corte", "Costa", "Croce",
+    "Fontana", "Grotta", "Guardia", "Mezza", "Palma", "Pietra", "Ripa", "Rocca", "Serra", "Torre",
+    "Val", "Valle", "Villa",
+];
+// Adjective stems that get a masculine or feminine ending
+const ITALIAN_2: &[&str] = &[
+    "bell", "bianc", "cald", "chiar", "cort", "ferrat", "fier", "fredd", "gioios", "grec", "guzz",
+    "lung", "long", "migli", "negr", "ner", "nov", "nuov", "ross", "rotond", "scur", "secc",
+    "sett", "vecchi",
+];
+const ITALIAN_2I: &[&str] = &[
+    "", "breve", "brevi", "chiari", "ferro", "fieschi", "fiore", "fonte", "forte", "gate", "leone",
+    "maggiore", "minore", "mole", "monte", "poli", "scuri", "terra", "torre", "vecchio", "verde",
+];
+const ITALIAN_3: &[&str] = &[
+    " Marittimo",
+    " Marittima",
+    " del Capo",
+    " del Monte",
+    " di Sopra",
+    " di Sotto",
+    " sui Monti",
+    " dei Marmi",
+    " dei Sassi",
+    " delle Fonti",
+    " sui Prati",
+    " a Mare",
+    " Superiore",
+    " Inferiore",
+    " Terme",
+    " Alta",
+    " Bassa",
+    " Brianza",
+    " Vesuviano",
+    " Scrivia",
+    " Ticino",
+];
+const ITALIAN_RIVER1: &[&str] = &[" del", " sul", " al", " nel"];
+const ITALIAN_RIVER2: &[&str] = &[
+    "l'Adda",
+    "l'Adige",
+    "le Alpi",
+    "l'Arno",
+    " Bormida",
+    " Brenta",
+    "la Dora Baltea",
+    " Lambro",
+    " Mincio",
+    " Naviglio",
+    " Oglio",
+    " Olona",
+    " Piave",
+    " Po",
+    " Reno",
+    " Secchia",
+    " Serio",
+    " Tagliamento",
+    " Tanaro",
+    " Taro",
+    " Ticino",
+    " Tevere",
+];
+
+fn italian(seed: u32) -> String {
+    if seed_mod_chance(0, 6, seed) == 0 {
+        return ITALIAN_REAL[seed_mod_chance(4, ITALIAN_REAL.len(), seed)].to_string();
+    }
+
+    let mut name = String::new();
+    if seed_mod_chance(0, 8, seed) == 0 {
+        name += ITALIAN_PREF[seed_mod_chance(11, ITALIAN_PREF.len(), seed)];
+    }
+    let feminine = seed_chance(0, 2, seed) != 0;
+    if feminine {
+        name += ITALIAN_1F[seed_mod_chance(4, ITALIAN_1F.len(), seed)];
+    } else {
+        name += ITALIAN_1M[seed_mod_chance(4, ITALIAN_1M.len(), seed)];
+    }
+    if seed_mod_chance(3, 3, seed) == 0 {
+        name += ITALIAN_2[seed_mod_chance(11, ITALIAN_2.len(), seed)];
+        name += if feminine { "a" } else { "o" };
+    } else {
+        name += ITALIAN_2I[seed_mod_chance(16, ITALIAN_2I.len(), seed)];
+    }
+    if seed_mod_chance(15, 4, seed) == 0 {
+        if seed_mod_chance(5, 2, seed) == 0 {
+            name += ITALIAN_3[seed_mod_chance(4, ITALIAN_3.len(), seed)];
+        } else {
+            name += ITALIAN_RIVER1[seed_mod_chance(4, ITALIAN_RIVER1.len(), seed)];
+            name += ITALIAN_RIVER2[seed_mod_chance(16, ITALIAN_RIVER2.len(), seed)];
+        }
+    }
+    name
+}
+
+const CATALAN_REAL: &[&str] = &[
+    "Barcelona",
+    "Hospitalet",
+    "Cerdanyola",
+    "Martorell",
+    "Badalona",
+    "Tarragona",
+    "Lleida",
+    "Girona",
+    "Sabadell",
+    "Terrassa",
+    "Reus",
+    "Valls",
+    "Vic",
+    "Vielha",
+    "Amposta",
+    "Tortosa",
+    "Berga",
+    "Olot",
+    "Mollerussa",
+    "Banyoles",
+    "Figueres",
+    "Balaguer",
+    "Vilafranca del Penedès",
+    "La Seu d'Urgell",
+    "Pont de Suert",
+    "Igualada",
+    "Manresa",
+    "Solsona",
+    "Les Borges Blanques",
+    "Tremp",
+    "Sort",
+    "Colera",
+    "Portbou",
+    "El Vendrell",
+    "Falset",
+    "Ripoll",
+    "Cervera",
+    "Gandesa",
+    "Mataró",
+    "Montblanc",
+    "Vilanova i la Geltrú",
+    "Tàrrega",
+    "Camprodon",
+    "Campdevànol",
+    "Cambrils",
+    "Begur",
+    "Setcases",
+    "Palafrugell",
+    "Begues",
+    "El Bruc",
+    "Cadaqués",
+    "Collbató",
+    "Cervelló",
+    "Esparreguera",
+    "Abrera",
+    "Alp",
+    "Das",
+    "Cercs",
+    "Manlleu",
+    "Masnou",
+    "Molins de Rei",
+    "Monistrol",
+    "Rocallaura",
+    "Rubí",
+    "Sitges",
+    "Roses",
+    "Sant Feliu de Guíxols",
+    "Sant Cugat del Vallès",
+    "Sant Boi de Llobregat",
+    "Tiana",
+    "Tossa de Mar",
+    "Vilassar de Mar",
+];
+const CATALAN_PREF: &[&str] = &[
+    "Pont de ",
+    "Parets de ",
+    "Canet de ",
+    "Castellar de ",
+    "Corbera de ",
+    "Riudarenes de ",
+    "Sant Esteve de ",
+    "Santa Maria de ",
+    "Sant Joan de ",
+    "Vilanova de ",
+    "Castell de ",
+    "Sant Climent de ",
+    "Sant Feliu de ",
+    "Sant Martí de ",
+    "Santa Coloma de ",
+];
+const CATALAN_1M: &[&str] = &["Torrent", "Cami", "Mont", "Bell", "Puig", "Riu"];
+const CATALAN_1F: &[&str] = &[
+    "Aigua", "Selva ", "Vall", "Serra", "Torre", "Granja", "Ciutat", "Vila",
+];
+const CATALAN_2M: &[&str] = &[
+    "alt", "baix", "fosc", "pelat", "vell", "nou", "gros", "gras", "prim", "fort", "alegre", "sec",
+    "mort",
+];
+const CATALAN_2F: &[&str] = &[
+    "baixa", "alta", "fosca", "clara", "nova", "vella", "grossa", "bella", "seca", "morta",
+];
+const CATALAN_3: &[&str] = &[
+    " despoblat",
+    " amagat",
+    " sobirà",
+    " de la Serra",
+    " de Munt",
+    " de Vall",
+];
+const CATALAN_RIVER1: &[&str] = &[
+    " d'Anoia",
+    " de Ter",
+    " de Llobregat",
+    " d'Ebre",
+    " de Segre",
+    " de Francolí",
+];
+
+fn catalan(seed: u32) -> String {
+    if seed_mod_chance(0, 3, seed) == 0 {
+        return CATALAN_REAL[seed_mod_chance(4, CATALAN_REAL.len(), seed)].to_string();
+    }
+
+    let mut name = String::new();
+    if seed_mod_chance(0, 2, seed) == 0 {
+        name += CATALAN_PREF[seed_mod_chance(11, CATALAN_PREF.len(), seed)];
+    }
+    if seed_chance(0, 2, seed) == 0 {
+        name += CATALAN_1M[seed_mod_chance(4, CATALAN_1M.len(), seed)];
+        name += CATALAN_2M[seed_mod_chance(11, CATALAN_2M.len(), seed)];
+    } else {
+        name += CATALAN_1F[seed_mod_chance(4, CATALAN_1F.len(), seed)];
+        name += CATALAN_2F[seed_mod_chance(11, CATALAN_2F.len(), seed)];
+    }
+    if seed_mod_chance(15, 5, seed) == 0 {
+        if seed_mod_chance(5, 2, seed) == 0 {
+            name += CATALAN_3[seed_mod_chance(4, CATALAN_3.len(), seed)];
+        } else {
+            name += CATALAN_RIVER1[seed_mod_chance(4, CATALAN_RIVER1.len(), seed)];
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::town_names::{generate_town_name, seed_chance, TownNameStyle};
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_seed_chance() {
+        assert_eq!(seed_chance(0, 10, 0xFFFF), 9);
+        assert_eq!(seed_chance(4, 10, 0x8000_0000), 0);
+        assert_eq!(seed_chance(16, 4, 0x8000_0000), 2);
+    }
+
+    #[test]
+    fn test_town_name_style() {
+        assert_eq!(
+            TownNameStyle::from_string_id(0x20C2),
+            Some(TownNameStyle::German)
+        );
+        assert_eq!(
+            TownNameStyle::from_string_id(0x20CD),
+            Some(TownNameStyle::Austrian)
+        );
+        assert_eq!(TownNameStyle::from_string_id(0x20BF), None);
+    }
+
+    #[test]
+    fn test_english_original_names() {
+        let name = |seed| generate_town_name(TownNameStyle::EnglishOriginal, seed);
+        // "Wrarnville" gets its start replaced
+        assert_eq!(name(0), "Invenville");
+        assert_eq!(name(0xFFFF_FFFF), "Fort Wenfingburg Springs");
+    }
+
+    #[test]
+    fn test_town_names_per_style() {
+        let cases = [
+            (TownNameStyle::French, 0xDEAD_BEEF, "Chambéry"),
+            (
+                TownNameStyle::German,
+                0x7AEE_008C,
+                "Quedlinfurt an der Donau",
+            ),
+            // "Drarnston" gets its start replaced
+            (TownNameStyle::EnglishAdditional, 0x9210_049C, "Quarnston"),
+            (TownNameStyle::LatinAmerican, 0xDEAD_BEEF, "La Quiaca"),
+            (TownNameStyle::Silly, 0xDEAD_BEEF, "Slimeygate"),
+            (TownNameStyle::Swedish, 0x4F1B_88E2, "Grönhammar"),
+            (TownNameStyle::Dutch, 0x4F1B_88E2, "Purmsdrecht"),
+            (TownNameStyle::Finnish, 0x4F1B_88E2, "Kivelä"),
+            (TownNameStyle::Polish, 0xDEAD_BEEF, "Ruda Wielka"),
+            (TownNameStyle::Slovak, 0x4F1B_88E2, "Piešťany"),
+            (TownNameStyle::Norwegian, 0x1234_5678, "Fridfoss"),
+            (TownNameStyle::Hungarian, 0x9210_049C, "Nagy-Bodrogegyháza"),
+            (
+                TownNameStyle::Austrian,
+                0xDEAD_BEEF,
+                "Bad Heiligenhaag ob der Gail",
+            ),
+            (TownNameStyle::Romanian, 0x1234_5678, "Drăgășani"),
+            (TownNameStyle::Czech, 0xDEAD_BEEF, "Bořetinka"),
+            (TownNameStyle::Swiss, 0xDEAD_BEEF, "Sitten"),
+            (TownNameStyle::Danish, 0x4F1B_88E2, "Christianshavn"),
+            (TownNameStyle::Turkish, 0x1234_5678, "Uluyer"),
+            (TownNameStyle::Italian, 0x9210_049C, "Casalbello Marittima"),
+            (TownNameStyle::Catalan, 0x9210_049C, "Vilanova de Camisec"),
+        ];
+        for (style, seed, name) in cases {
+            assert_eq!(generate_town_name(style, seed), name, "{:?}", style);
+        }
+    }
+
+    #[test]
+    fn test_town_names_are_unique() {
+        // The game only founds towns whose generated name is unused and shorter than 32 characters
+        for path in ["./test_v196.sav", "./test-big.sav"] {
+            let towns = load(path).towns().unwrap();
+            let names: HashSet<&str> = towns.iter().map(|town| town.name.as_str()).collect();
+            assert_eq!(names.len(), towns.len());
+            assert!(names.iter().all(|name| name.chars().count() < 32));
+        }
+        let save_game = load("./test_v196.sav");
+        assert_eq!(save_game.towns().unwrap()[0].name, "Eisenach ob der Mur");
+    }
+}
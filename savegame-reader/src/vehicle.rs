@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::common::{has_bit, to_percent16, ORIGINAL_BASE_YEAR, SLV_31};
+use crate::error::{Result, SaveGameError};
+use crate::save_file::SaveVersion;
+use crate::table_reader::TableItem;
+
+// Profits are stored with 8 bits of fractions since this version
+const SLV_88: u16 = 88;
+
+// Vehicle::vehstatus
+const VS_HIDDEN: u8 = 0;
+const VS_STOPPED: u8 = 1;
+const VS_CRASHED: u8 = 7;
+
+#[wasm_bindgen(typescript_custom_section)]
+const VEHICLE_TS: &'static str = r#"
+export type VehicleType = "Train" | "Road" | "Ship" | "Aircraft" | "Effect" | "Disaster";
+
+export interface Vehicle {
+  index: number;
+  vehicle_type: VehicleType;
+  subtype: number;
+  owner?: number;
+  tile: number;
+  x: number;
+  y: number;
+  z: number;
+  hidden: boolean;
+  next?: number;
+  engine?: number;
+  unit_number?: number;
+  name?: string;
+  stopped?: boolean;
+  crashed?: boolean;
+  cargo_type?: number;
+  cargo_capacity?: number;
+  cargo_count?: number;
+  age?: number;
+  max_age?: number;
+  build_year?: number;
+  reliability?: number;
+  profit_this_year?: number;
+  profit_last_year?: number;
+  value?: number;
+  orders?: number;
+}
+"#;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum VehicleType {
+    Train,
+    Road,
+    Ship,
+    Aircraft,
+    Effect,
+    Disaster,
+}
+
+impl VehicleType {
+    pub fn from_byte(byte: u8) -> Option<VehicleType> {
+        Some(match byte {
+            0 => VehicleType::Train,
+            1 => VehicleType::Road,
+            2 => VehicleType::Ship,
+            3 => VehicleType::Aircraft,
+            4 => VehicleType::Effect,
+            5 => VehicleType::Disaster,
+            _ => return None,
+        })
+    }
+
+    // The key the vehicle's fields are nested under in VEHS
+    fn key(self) -> &'static str {
+        match self {
+            VehicleType::Train => "train",
+            VehicleType::Road => "roadveh",
+            VehicleType::Ship => "ship",
+            VehicleType::Aircraft => "aircraft",
+            VehicleType::Effect => "effect",
+            VehicleType::Disaster => "disaster",
+        }
+    }
+}
+
+// The fields only trains, road vehicles, ships and aircraft have. `reliability` goes from 0 to
+// 65535 like in OpenTTD.
+#[derive(Debug, Clone, Serialize)]
+pub struct VehicleDetails {
+    pub engine: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit_number: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub stopped: bool,
+    pub crashed: bool,
+    pub cargo_type: u8,
+    pub cargo_capacity: u16,
+    pub cargo_count: u32,
+    // Ages are in days
+    pub age: i32,
+    pub max_age: i32,
+    pub build_year: i32,
+    pub reliability: u16,
+    pub profit_this_year: i64,
+    pub profit_last_year: i64,
+    pub value: i64,
+    // The shared order list, or the first order in savegames from before order lists
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orders: Option<usize>,
+}
+
+// A single vehicle part. Trains and articulated vehicles are chains of parts linked by `next`,
+// with only the first part having a unit number. `x`, `y` and `z` are in pixels, with 16 pixels
+// to a tile.
+#[derive(Debug, Clone, Serialize)]
+pub struct Vehicle {
+    pub index: usize,
+    pub vehicle_type: VehicleType,
+    pub subtype: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<u8>,
+    pub tile: u32,
+    pub x: i64,
+    pub y: i64,
+    pub z: i32,
+    pub hidden: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<usize>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub details: Option<VehicleDetails>,
+}
+
+// Parts of other chunks a vehicle refers to
+pub struct VehicleContext {
+    pub version: SaveVersion,
+    // The cargo count of each cargo packet
+    pub packets: HashMap<usize, u32>,
+}
+
+impl Vehicle {
+    pub fn from_item(item: &TableItem, context: &VehicleContext) -> Result<Vehicle> {
+        let vehicle_type = item
+            .integer("type")
+            .and_then(|byte| VehicleType::from_byte(byte as u8))
+            .ok_or(SaveGameError::InvalidField(String::from("type")))?;
+        let vehicle = item
+            .child(vehicle_type.key())
+            .ok_or(SaveGameError::InvalidField(String::from(
+                vehicle_type.key(),
+            )))?;
+        // Effect vehicles and disasters keep their fields at the top, the others in `common`
+        let common = vehicle.child("common");
+        let fields = common.unwrap_or(vehicle);
+        let status: u8 = fields.field("vehstatus")?;
+
+        Ok(Vehicle {
+            index: item.index,
+            vehicle_type,
+            subtype: fields.field("subtype")?,
+            owner: fields.integer("owner").map(|owner| owner as u8),
+            tile: fields.field("tile")?,
+            x: fields.field("x_pos")?,
+            y: fields.field("y_pos")?,
+            z: fields.field("z_pos")?,
+            hidden: has_bit(usize::from(status), VS_HIDDEN),
+            next: fields.reference("next"),
+            details: common
+                .map(|common| VehicleDetails::from_item(common, status, context))
+                .transpose()?,
+        })
+    }
+}
+
+impl VehicleDetails {
+    fn from_item(item: &TableItem, status: u8, context: &VehicleContext) -> Result<VehicleDetails> {
+        // Cargo used to be a plain count before it was split into packets
+        let cargo_count = match item.integer("cargo_count") {
+            Some(count) => count as u32,
+            None => item
+                .references("cargo.packets")
+                .filter_map(|packet| context.packets.get(&packet))
+                .sum(),
+        };
+        let mut build_year: i32 = item.field("build_year")?;
+        if context.version.is_before(SLV_31) {
+            build_year += ORIGINAL_BASE_YEAR;
+        }
+        let profit = |key| -> Result<i64> {
+            let profit: i64 = item.field(key)?;
+            Ok(if context.version.is_before(SLV_88) {
+                profit
+            } else {
+                profit >> 8
+            })
+        };
+
+        Ok(VehicleDetails {
+            engine: item.field("engine_type")?,
+            unit_number: item
+                .integer("unitnumber")
+                .filter(|unit_number| *unit_number != 0)
+                .map(|unit_number| unit_number as u16),
+            name: item.string("name").map(String::from),
+            stopped: has_bit(usize::from(status), VS_STOPPED),
+            crashed: has_bit(usize::from(status), VS_CRASHED),
+            cargo_type: item.field("cargo_type")?,
+            cargo_capacity: item.field("cargo_cap")?,
+            cargo_count,
+            age: item.field("age")?,
+            max_age: item.field("max_age")?,
+            build_year,
+            reliability: item.field("reliability")?,
+            profit_this_year: profit("profit_this_year")?,
+            profit_last_year: profit("profit_last_year")?,
+            value: item.field("value")?,
+            orders: item.reference("orders"),
+        })
+    }
+
+    pub fn reliability_percent(&self) -> u8 {
+        to_percent16(self.reliability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::save_file::CompressedSaveFile;
+    use crate::save_game::SaveGame;
+    use crate::vehicle::VehicleType;
+
+    fn load(path: &str) -> SaveGame {
+        let file = File::open(path).unwrap();
+        SaveGame::load(CompressedSaveFile::new(file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_vehicles_from_legacy_chunks() {
+        let save_game = load("./test_v196.sav");
+        let vehicles = save_game.vehicles().unwrap();
+        assert_eq!(vehicles.len(), 924);
+        // Every part a vehicle links to exists
+        assert!(vehicles
+            .iter()
+            .filter_map(|vehicle| vehicle.next)
+            .all(|next| vehicles.iter().any(|vehicle| vehicle.index == next)));
+        assert!(vehicles
+            .iter()
+            .filter_map(|vehicle| vehicle.details.as_ref())
+            .any(|details| details.cargo_count > 0));
+        assert!(vehicles
+            .iter()
+            .filter_map(|vehicle| vehicle.details.as_ref())
+            .all(|details| details.build_year >= 1920 && details.reliability_percent() <= 100));
+    }
+
+    #[test]
+    fn test_vehicles_from_tables() {
+        let save_game = load("./test-big.sav");
+        let (width, height) = save_game.map_size().unwrap();
+        let vehicles = save_game.vehicles().unwrap();
+        assert!(
+            vehicles
+                .iter()
+                .filter(|vehicle| vehicle.vehicle_type != VehicleType::Disaster)
+                .all(|vehicle| vehicle.x / 16 < i64::from(width)
+                    && vehicle.y / 16 < i64::from(height))
+        );
+        let details = vehicles
+            .iter()
+            .filter_map(|vehicle| vehicle.details.as_ref())
+            .collect::<Vec<_>>();
+        assert!(details.iter().any(|details| details.unit_number.is_some()));
+        assert!(details
+            .iter()
+            .all(|details| details.reliability_percent() <= 100
+                && details.cargo_count <= u32::from(details.cargo_capacity)));
+    }
+}